@@ -0,0 +1,144 @@
+use std::fmt::Display;
+
+use utils::config::Params;
+
+// A part takes the puzzle input and its parameters (see utils::config), and returns the
+// answer (or why it couldn't find one).
+pub type Part = fn(&str, &Params) -> Result<String, String>;
+
+pub struct Day {
+  pub number: u32,
+  pub part1: Part,
+  pub part2: Part,
+}
+
+pub static DAYS: [Day; 13] = [
+  Day {
+    number: 1,
+    part1: |input, _| day1::max_calories(input.lines()).map(|c| c.to_string()).map_err(error),
+    part2: |input, _| day1::max_3_calories(input.lines()).map(|c| c.iter().map(|c| *c as u128).sum::<u128>().to_string()).map_err(error),
+  },
+  Day {
+    number: 2,
+    part1: |input, _| day2::predict_score_from_move(input.lines()).map(|s| s.to_string()).map_err(error),
+    part2: |input, _| day2::predict_score_from_result(input.lines()).map(|s| s.to_string()).map_err(error),
+  },
+  Day {
+    number: 3,
+    part1: |input, _| day3::score_duplicate_item(input.lines(), day3::Alphabet::Letters).map(|s| s.to_string()).map_err(error),
+    part2: |input, params| {
+      day3::score_groups_unique_elements(input, param(params, "group_size", day3::GROUP_SIZE)?, day3::Alphabet::Letters)
+        .map(|s| s.to_string())
+        .map_err(error)
+    },
+  },
+  Day {
+    number: 4,
+    part1: |input, _| day4::count_fully_contained_pairs(input.lines()).map(|c| c.to_string()).map_err(error),
+    part2: |input, _| day4::count_overlapping_pairs(input.lines()).map(|c| c.to_string()).map_err(error),
+  },
+  Day {
+    number: 5,
+    part1: |input, _| day5::top_of_stacks_9000(input).map_err(error),
+    part2: |input, _| day5::top_of_stacks_9001(input).map_err(error),
+  },
+  Day {
+    number: 6,
+    part1: |input, params| marker(input, param(params, "packet_marker", day6::PACKET_MARKER)?),
+    part2: |input, params| marker(input, param(params, "message_marker", day6::MESSAGE_MARKER)?),
+  },
+  Day {
+    number: 7,
+    part1: |input, params| {
+      let max_dir_size = param(params, "max_dir_size", day7::MAX_DIR_SIZE)?;
+      let tree = day7::build_tree(&mut input.lines().peekable()).map_err(|e| e.message)?;
+      day7::part1(&tree, max_dir_size).map(|s| s.to_string()).map_err(|e| e.message)
+    },
+    part2: |input, params| {
+      let disk_size = param(params, "disk_size", day7::DISK_SIZE)?;
+      let needed_space = param(params, "needed_space", day7::NEEDED_SPACE)?;
+      let tree = day7::build_tree(&mut input.lines().peekable()).map_err(|e| e.message)?;
+      day7::part2(&tree, disk_size, needed_space).map(|(_, size)| size.to_string()).map_err(|e| e.message)
+    },
+  },
+  Day {
+    number: 8,
+    part1: |input, _| day8::count_visible_trees(input.lines()).map(|c| c.to_string()).map_err(|e| e.message),
+    part2: |input, _| day8::highest_scenic_score(input.lines()).map(|s| s.to_string()).map_err(|e| e.message),
+  },
+  Day {
+    number: 9,
+    part1: |input, _| {
+      let commands = day9::parse_commands(input.lines()).map_err(|e| e.message)?;
+      Ok(day9::simulate_movements(&commands, 2).to_string())
+    },
+    part2: |input, _| {
+      let commands = day9::parse_commands(input.lines()).map_err(|e| e.message)?;
+      Ok(day9::simulate_movements(&commands, 10).to_string())
+    },
+  },
+  Day {
+    number: 10,
+    part1: |input, params| {
+      let first = param(params, "first_sample", day10::FIRST_SAMPLE)?;
+      let interval = param(params, "sample_interval", day10::SAMPLE_INTERVAL)?;
+      let instructions = day10::parse_instructions(input.lines()).map_err(|e| format!("{:#}", e))?;
+      Ok(day10::signal_strength(&day10::register_values(&instructions), first, interval).to_string())
+    },
+    part2: |input, params| {
+      let width = param(params, "crt_width", day10::CRT_WIDTH)?;
+      let height = param(params, "crt_height", day10::CRT_HEIGHT)?;
+      let instructions = day10::parse_instructions(input.lines()).map_err(|e| format!("{:#}", e))?;
+      Ok(day10::render_crt(&day10::register_values(&instructions), width, height).join("\n"))
+    },
+  },
+  Day {
+    number: 13,
+    part1: |input, _| day13::part1(input).map(|s| s.to_string()).map_err(|e| e.message),
+    part2: |input, _| day13::part2(input).map(|s| s.to_string()).map_err(|e| e.message),
+  },
+  Day {
+    number: 14,
+    part1: |input, params| {
+      let spigot_x = param(params, "spigot_x", day14::SPIGOT.x)?;
+      let paths = day14::parse_input(input.lines()).map_err(|e| e.message)?;
+      day14::min_units_for_infinite_flow(&paths, spigot_x).map(|(units, _)| units.to_string()).map_err(|e| e.message)
+    },
+    part2: |input, params| {
+      let spigot_x = param(params, "spigot_x", day14::SPIGOT.x)?;
+      let paths = day14::parse_input(input.lines()).map_err(|e| e.message)?;
+      day14::min_units_until_full(&paths, spigot_x).map(|(units, _)| units.to_string()).map_err(|e| e.message)
+    },
+  },
+  Day {
+    number: 15,
+    part1: |input, params| {
+      let row = param(params, "row", day15::ROW)?;
+      let pairs = day15::parse_lines(input.lines()).map_err(|e| e.message)?;
+      Ok(day15::impossible_positions(&pairs, row).to_string())
+    },
+    part2: |input, params| {
+      let max_side = param(params, "max_side", day15::MAX_SIDE)?;
+      let pairs = day15::parse_lines(input.lines()).map_err(|e| e.message)?;
+      day15::find_open_position(&pairs, max_side)
+        .map(|p| p.tuning_frequency().to_string())
+        .ok_or("no open position".to_string())
+    },
+  },
+];
+
+pub fn find(number: u32) -> Option<&'static Day> {
+  DAYS.iter().find(|day| day.number == number)
+}
+
+fn param<T: TryFrom<i64>>(params: &Params, key: &str, default: T) -> Result<T, String> {
+  params.get(key, default).map_err(|e| e.message)
+}
+
+fn marker(input: &str, n: usize) -> Result<String, String> {
+  day6::find_marker(input, n).map(|i| i.to_string()).ok_or(format!("no marker of {} in the signal", n))
+}
+
+fn error(e: impl Display) -> String {
+  e.to_string()
+}
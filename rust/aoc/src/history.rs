@@ -0,0 +1,303 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use utils::config::Params;
+
+use crate::runner::{DayReport, Outcome};
+
+// Every part of every run, so that answers and timings can be compared between runs.
+pub struct History {
+  conn: Connection,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+  pub part: u32,
+  pub input_hash: String,
+  // None if the part failed, timed out or never ran.
+  pub answer: Option<String>,
+  pub outcome: String,
+  pub elapsed: Option<Duration>,
+  pub commit: Option<String>,
+  pub recorded_at: String,
+}
+
+impl History {
+  pub fn open(path: &Path) -> rusqlite::Result<History> {
+    History::init(Connection::open(path)?)
+  }
+
+  #[cfg(test)]
+  fn open_in_memory() -> rusqlite::Result<History> {
+    History::init(Connection::open_in_memory()?)
+  }
+
+  fn init(conn: Connection) -> rusqlite::Result<History> {
+    conn.execute_batch(
+      "CREATE TABLE IF NOT EXISTS runs (
+        id INTEGER PRIMARY KEY,
+        day INTEGER NOT NULL,
+        part INTEGER NOT NULL,
+        input_hash TEXT NOT NULL,
+        answer TEXT,
+        outcome TEXT NOT NULL,
+        elapsed_us INTEGER,
+        git_commit TEXT,
+        recorded_at INTEGER NOT NULL
+      );
+      CREATE INDEX IF NOT EXISTS runs_by_input ON runs (day, part, input_hash);",
+    )?;
+    Ok(History { conn })
+  }
+
+  // Records both parts of a day, returning a warning for each answer that differs from the
+  // last one found for the same input.
+  pub fn record(&self, report: &DayReport, input_hash: &str, commit: Option<&str>) -> rusqlite::Result<Vec<String>> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+    self.record_at(report, input_hash, commit, now)
+  }
+
+  fn record_at(
+    &self,
+    report: &DayReport,
+    input_hash: &str,
+    commit: Option<&str>,
+    now: i64,
+  ) -> rusqlite::Result<Vec<String>> {
+    let mut warnings = vec![];
+    for (i, part) in report.parts.iter().enumerate() {
+      let number = i as u32 + 1;
+      let answer = match &part.outcome {
+        Outcome::Answer(answer) => Some(answer),
+        _ => None,
+      };
+      if let Some(answer) = answer {
+        let previous: Option<(String, Option<String>, String)> = self.conn.query_row(
+          "SELECT answer, git_commit, datetime(recorded_at, 'unixepoch') FROM runs
+            WHERE day = ?1 AND part = ?2 AND input_hash = ?3 AND answer IS NOT NULL
+            ORDER BY id DESC LIMIT 1",
+          params![report.day, number, input_hash],
+          |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        ).optional()?;
+        if let Some((previous, previous_commit, recorded_at)) = previous {
+          if &previous != answer {
+            warnings.push(format!(
+              "day {} part {}: answer {} differs from {} at {} ({}) for the same input",
+              report.day, number, oneline(answer), oneline(&previous), recorded_at,
+              previous_commit.as_deref().unwrap_or("unknown commit"),
+            ));
+          }
+        }
+      }
+      self.conn.execute(
+        "INSERT INTO runs (day, part, input_hash, answer, outcome, elapsed_us, git_commit, recorded_at)
+          VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+          report.day,
+          number,
+          input_hash,
+          answer,
+          outcome_name(&part.outcome),
+          part.elapsed.map(|e| e.as_micros() as i64),
+          commit,
+          now,
+        ],
+      )?;
+    }
+    Ok(warnings)
+  }
+
+  // Every run of a day, oldest first.
+  pub fn records(&self, day: u32) -> rusqlite::Result<Vec<Record>> {
+    let mut statement = self.conn.prepare(
+      "SELECT part, input_hash, answer, outcome, elapsed_us, git_commit, datetime(recorded_at, 'unixepoch')
+        FROM runs WHERE day = ?1 ORDER BY part, input_hash, id",
+    )?;
+    let records = statement.query_map([day], |row| {
+      Ok(Record {
+        part: row.get(0)?,
+        input_hash: row.get(1)?,
+        answer: row.get(2)?,
+        outcome: row.get(3)?,
+        elapsed: row.get::<_, Option<i64>>(4)?.map(|us| Duration::from_micros(us as u64)),
+        commit: row.get(5)?,
+        recorded_at: row.get(6)?,
+      })
+    })?;
+    records.collect()
+  }
+}
+
+fn outcome_name(outcome: &Outcome) -> &'static str {
+  match outcome {
+    Outcome::Answer(_) => "ok",
+    Outcome::Failed(_) => "failed",
+    Outcome::TimedOut => "timed out",
+    Outcome::NotRun => "not run",
+  }
+}
+
+// day 10's answer is drawn over several lines, which doesn't fit in a warning.
+fn oneline(answer: &str) -> String {
+  answer.lines().collect::<Vec<_>>().join("/")
+}
+
+// Identifies what a day was asked to solve: the input, and any parameters that were set,
+// since those change the answer too.
+//
+// FNV-1a, which (unlike std's hasher) is guaranteed to give the same hash on every machine
+// and every version of rust.
+pub fn input_hash(input: &str, params: &Params) -> String {
+  let params = params.to_string();
+  let bytes = if params.is_empty() { input.to_string() } else { format!("{}\n{}", params, input) };
+  let hash = bytes.bytes().fold(0xcbf29ce484222325_u64, |hash, b| (hash ^ b as u64).wrapping_mul(0x100000001b3));
+  format!("{:016x}", hash)
+}
+
+// The commit the solutions were built from, marked if there are uncommitted changes.
+pub fn git_commit() -> Option<String> {
+  let output = Command::new("git").args(["describe", "--always", "--dirty"]).output().ok()?;
+  if !output.status.success() {
+    return None;
+  }
+  Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// Lists the runs for each part and input, showing an answer only when it changed, and how
+// each run's time compares to the one before. Only runs that found an answer count towards
+// the timings, since a timed out run only says how long it was given.
+pub fn format_records(day: u32, records: &[Record]) -> String {
+  let mut out = String::new();
+  if records.is_empty() {
+    writeln!(out, "no runs recorded for day {}", day).unwrap();
+    return out;
+  }
+
+  let mut start = 0;
+  while start < records.len() {
+    let (part, hash) = (records[start].part, &records[start].input_hash);
+    let end = start + records[start..].iter().take_while(|r| r.part == part && &r.input_hash == hash).count();
+    let group = &records[start..end];
+    writeln!(out, "day {} part {}, input {} ({} runs)", day, part, hash, group.len()).unwrap();
+
+    let mut last_answer: Option<&str> = None;
+    let mut last_elapsed: Option<Duration> = None;
+    for record in group {
+      let elapsed = record.elapsed.filter(|_| record.outcome == "ok");
+      let answer = match (&record.answer, last_answer) {
+        (Some(answer), Some(last)) if answer == last => "(same)".to_string(),
+        (Some(answer), _) => oneline(answer),
+        (None, _) => record.outcome.to_uppercase(),
+      };
+      let shown = if record.outcome == "timed out" { None } else { record.elapsed };
+      let shown = shown.map_or("-".to_string(), |e| format!("{:.1?}", e));
+      let trend = match (elapsed, last_elapsed) {
+        (Some(elapsed), Some(last)) if !last.is_zero() => {
+          format!("{:+.0}%", (elapsed.as_secs_f64() / last.as_secs_f64() - 1.0) * 100.0)
+        }
+        _ => String::new(),
+      };
+      let commit = record.commit.as_deref().unwrap_or("-");
+      let line = format!("  {}  {:<20}  {:<20}  {:>10}  {}", record.recorded_at, commit, answer, shown, trend);
+      writeln!(out, "{}", line.trim_end()).unwrap();
+      if record.answer.is_some() {
+        last_answer = record.answer.as_deref();
+      }
+      last_elapsed = elapsed.or(last_elapsed);
+    }
+
+    let mut timings: Vec<Duration> = group.iter().filter(|r| r.outcome == "ok").filter_map(|r| r.elapsed).collect();
+    timings.sort();
+    if let (Some(fastest), Some(slowest)) = (timings.first(), timings.last()) {
+      writeln!(
+        out,
+        "  fastest {:.1?}, median {:.1?}, slowest {:.1?}",
+        fastest, timings[timings.len() / 2], slowest
+      ).unwrap();
+    }
+    start = end;
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::runner::PartReport;
+  use utils::config::Config;
+
+  fn report(day: u32, part1: Outcome, part2: Outcome) -> DayReport {
+    let part = |outcome| PartReport { outcome, elapsed: Some(Duration::from_millis(10)) };
+    DayReport { day, parts: [part(part1), part(part2)] }
+  }
+
+  #[test]
+  pub fn test_warns_on_changed_answers() {
+    let history = History::open_in_memory().unwrap();
+    let first = report(15, Outcome::Answer("26".to_string()), Outcome::Answer("56000011".to_string()));
+    assert!(history.record_at(&first, "abc", Some("v1"), 0).unwrap().is_empty());
+    assert!(history.record_at(&first, "abc", Some("v2"), 60).unwrap().is_empty());
+
+    // a failure doesn't count as a change, or hide the last answer
+    let failed = report(15, Outcome::Answer("26".to_string()), Outcome::TimedOut);
+    assert!(history.record_at(&failed, "abc", Some("v3"), 120).unwrap().is_empty());
+
+    let changed = report(15, Outcome::Answer("27".to_string()), Outcome::Answer("56000011".to_string()));
+    assert_eq!(
+      vec!["day 15 part 1: answer 27 differs from 26 at 1970-01-01 00:02:00 (v3) for the same input".to_string()],
+      history.record_at(&changed, "abc", Some("v4"), 180).unwrap()
+    );
+    // the same answers for another input are fine
+    assert!(history.record_at(&changed, "def", None, 240).unwrap().is_empty());
+
+    let records = history.records(15).unwrap();
+    assert_eq!(10, records.len());
+    assert_eq!(Some("26".to_string()), records[0].answer);
+    assert_eq!(Some("v1".to_string()), records[0].commit);
+    assert!(history.records(14).unwrap().is_empty());
+  }
+
+  #[test]
+  pub fn test_format_records() {
+    let history = History::open_in_memory().unwrap();
+    let mut first = report(7, Outcome::Answer("95437".to_string()), Outcome::Failed("oops".to_string()));
+    history.record_at(&first, "abc", Some("v1"), 0).unwrap();
+    first.parts[0].elapsed = Some(Duration::from_millis(5));
+    history.record_at(&first, "abc", Some("v2"), 60).unwrap();
+    // a timeout's time is only how long it was given, so it doesn't count
+    let timed_out = PartReport { outcome: Outcome::TimedOut, elapsed: Some(Duration::from_secs(60)) };
+    history.record_at(&DayReport { day: 7, parts: [timed_out.clone(), timed_out] }, "abc", Some("v3"), 120).unwrap();
+    first.parts[0].elapsed = Some(Duration::from_millis(20));
+    history.record_at(&first, "abc", Some("v4"), 180).unwrap();
+
+    assert_eq!(
+"day 7 part 1, input abc (4 runs)
+  1970-01-01 00:00:00  v1                    95437                     10.0ms
+  1970-01-01 00:01:00  v2                    (same)                     5.0ms  -50%
+  1970-01-01 00:02:00  v3                    TIMED OUT                      -
+  1970-01-01 00:03:00  v4                    (same)                    20.0ms  +300%
+  fastest 5.0ms, median 10.0ms, slowest 20.0ms
+day 7 part 2, input abc (4 runs)
+  1970-01-01 00:00:00  v1                    FAILED                    10.0ms
+  1970-01-01 00:01:00  v2                    FAILED                    10.0ms
+  1970-01-01 00:02:00  v3                    TIMED OUT                      -
+  1970-01-01 00:03:00  v4                    FAILED                    10.0ms
+", format_records(7, &history.records(7).unwrap()));
+  }
+
+  #[test]
+  pub fn test_input_hash() {
+    let params = Params::default();
+    assert_eq!("cbf29ce484222325", input_hash("", &params));
+    assert_eq!("af63dc4c8601ec8c", input_hash("a", &params));
+    assert_ne!(input_hash("1\n2", &params), input_hash("2\n1", &params));
+
+    let mut config = Config::default();
+    config.set("day7.disk_size=100").unwrap();
+    assert_ne!(input_hash("a", &params), input_hash("a", &config.params(7, "day7.txt")));
+  }
+}
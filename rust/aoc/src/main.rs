@@ -0,0 +1,167 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+use utils::config::Config;
+
+use history::History;
+
+mod days;
+mod history;
+mod runner;
+
+const USAGE: &str = "usage:
+  aoc run all|<day>|<day>:<input file>... [--jobs N] [--timeout SECS] [--inputs DIR] \
+[--config FILE] [--set day<N>.<key>=<value>]... [--db FILE] [--no-history]
+  aoc history <day> [--db FILE]";
+
+const DEFAULT_DB: &str = "aoc-history.sqlite";
+
+enum Args {
+  Run(RunArgs),
+  History { day: u32, db: PathBuf },
+}
+
+struct RunArgs {
+  // each day, and the input file to solve it with if not the usual one.
+  days: Vec<(&'static days::Day, Option<PathBuf>)>,
+  inputs: PathBuf,
+  config: Config,
+  options: runner::Options,
+  // where to record the run, if anywhere.
+  db: Option<PathBuf>,
+}
+
+// NOTE: like utils::cli, parses the arguments by hand rather than pulling in clap.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+  match args.next().as_deref() {
+    Some("run") => parse_run_args(args).map(Args::Run),
+    Some("history") => {
+      let mut day = None;
+      let mut db = PathBuf::from(DEFAULT_DB);
+      while let Some(arg) = args.next() {
+        match arg.as_str() {
+          "--db" => db = PathBuf::from(args.next().ok_or("--db needs a value")?),
+          arg => day = Some(arg.parse().map_err(|_| format!("unknown argument {}\n{}", arg, USAGE))?),
+        }
+      }
+      Ok(Args::History { day: day.ok_or(USAGE)?, db })
+    }
+    _ => Err(USAGE.to_string()),
+  }
+}
+
+fn parse_run_args(mut args: impl Iterator<Item = String>) -> Result<RunArgs, String> {
+  let mut days = vec![];
+  let mut inputs = PathBuf::from("inputs");
+  let mut config_path = None;
+  let mut overrides = vec![];
+  let mut db = Some(PathBuf::from(DEFAULT_DB));
+  let mut options = runner::Options {
+    jobs: thread::available_parallelism().map_or(1, |n| n.get()),
+    timeout: Duration::from_secs(60),
+  };
+  while let Some(arg) = args.next() {
+    let mut value = |flag: &str| args.next().ok_or(format!("{} needs a value", flag));
+    match arg.as_str() {
+      "--jobs" => {
+        options.jobs = value("--jobs")?.parse().map_err(|_| "--jobs must be a number".to_string())?;
+      }
+      "--timeout" => {
+        let secs: f64 = value("--timeout")?.parse().map_err(|_| "--timeout must be a number".to_string())?;
+        options.timeout = Duration::try_from_secs_f64(secs).map_err(|_| "--timeout must be positive".to_string())?;
+      }
+      "--inputs" => inputs = PathBuf::from(value("--inputs")?),
+      "--config" => config_path = Some(PathBuf::from(value("--config")?)),
+      "--set" => overrides.push(value("--set")?),
+      "--db" => db = Some(PathBuf::from(value("--db")?)),
+      "--no-history" => db = None,
+      "all" => days.extend(days::DAYS.iter().map(|day| (day, None))),
+      day => {
+        let (day, input) = match day.split_once(':') {
+          Some((day, input)) => (day, Some(PathBuf::from(input))),
+          None => (day, None),
+        };
+        let number = day.parse().map_err(|_| format!("unknown argument {}\n{}", day, USAGE))?;
+        days.push((days::find(number).ok_or(format!("no solution for day {}", number))?, input));
+      }
+    }
+  }
+  if days.is_empty() {
+    return Err(USAGE.to_string());
+  }
+  days.sort_by_key(|(day, _)| day.number);
+
+  // the config file is optional, unless asked for by name.
+  let mut config = match config_path {
+    Some(path) => Config::load(&path).map_err(|e| e.message)?,
+    None => Config::load_default().map_err(|e| e.message)?,
+  };
+  for spec in overrides {
+    config.set(&spec).map_err(|e| e.message)?;
+  }
+  Ok(RunArgs { days, inputs, config, options, db })
+}
+
+fn main() -> ExitCode {
+  match parse_args(std::env::args().skip(1)) {
+    Ok(Args::Run(args)) => run(args),
+    Ok(Args::History { day, db }) => match History::open(&db).and_then(|history| history.records(day)) {
+      Ok(records) => {
+        print!("{}", history::format_records(day, &records));
+        ExitCode::SUCCESS
+      }
+      Err(e) => {
+        eprintln!("could not read history from {}: {}", db.display(), e);
+        ExitCode::FAILURE
+      }
+    },
+    Err(message) => {
+      eprintln!("{}", message);
+      ExitCode::FAILURE
+    }
+  }
+}
+
+fn run(args: RunArgs) -> ExitCode {
+  let mut jobs = vec![];
+  for (day, input) in args.days {
+    let path = input.unwrap_or_else(|| args.inputs.join(format!("day{}.txt", day.number)));
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    match std::fs::read_to_string(&path) {
+      Ok(input) => jobs.push(runner::Job { day, input, params: args.config.params(day.number, &name) }),
+      Err(e) => {
+        eprintln!("could not read {}: {}", path.display(), e);
+        return ExitCode::FAILURE;
+      }
+    }
+  }
+
+  let input_hashes: Vec<_> = jobs.iter().map(|job| history::input_hash(&job.input, &job.params)).collect();
+  let reports = runner::run(jobs, &args.options);
+  print!("{}", runner::summary(&reports));
+
+  // a run that can't be recorded still has its answers, so only warn about it.
+  if let Some(db) = args.db {
+    let commit = history::git_commit();
+    let recorded = History::open(&db).and_then(|history| {
+      for (report, input_hash) in reports.iter().zip(&input_hashes) {
+        for warning in history.record(report, input_hash, commit.as_deref())? {
+          eprintln!("warning: {}", warning);
+        }
+      }
+      Ok(())
+    });
+    if let Err(e) = recorded {
+      eprintln!("warning: could not record the run in {}: {}", db.display(), e);
+    }
+  }
+
+  // returning from main also stops any parts that are still running after timing out.
+  if reports.iter().all(|r| r.is_ok()) {
+    ExitCode::SUCCESS
+  } else {
+    ExitCode::FAILURE
+  }
+}
@@ -0,0 +1,264 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use utils::config::Params;
+
+use crate::days::Day;
+
+pub struct Options {
+  pub jobs: usize,
+  // how long a day (both parts) gets before we give up on it.
+  pub timeout: Duration,
+}
+
+// A day to solve, with its input and parameters.
+pub struct Job {
+  pub day: &'static Day,
+  pub input: String,
+  pub params: Params,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+  Answer(String),
+  Failed(String),
+  TimedOut,
+  // the part never started, because an earlier part timed out.
+  NotRun,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReport {
+  pub outcome: Outcome,
+  pub elapsed: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DayReport {
+  pub day: u32,
+  pub parts: [PartReport; 2],
+}
+
+enum Event {
+  Started(usize),
+  Finished(usize, Result<String, String>, Duration),
+}
+
+// Runs each day on a pool of `options.jobs` threads, and returns their reports in the same
+// order as the jobs.
+//
+// A day's parts run on a thread of their own, so that a part that hangs can be abandoned when
+// the day's timeout runs out. There's no way to stop that thread, so it keeps running until
+// the process exits.
+pub fn run(jobs: Vec<Job>, options: &Options) -> Vec<DayReport> {
+  let jobs = Arc::new(jobs);
+  let next = Arc::new(AtomicUsize::new(0));
+  let (tx, rx) = mpsc::channel();
+
+  let workers: Vec<_> = (0..options.jobs.max(1))
+    .map(|_| {
+      let (jobs, next, tx, timeout) = (jobs.clone(), next.clone(), tx.clone(), options.timeout);
+      thread::spawn(move || {
+        loop {
+          let i = next.fetch_add(1, Ordering::SeqCst);
+          let Some(job) = jobs.get(i) else { break };
+          let report = run_day(job.day, job.input.clone(), job.params.clone(), timeout);
+          eprintln!("{}", report.status());
+          if tx.send((i, report)).is_err() {
+            break;
+          }
+        }
+      })
+    })
+    .collect();
+  drop(tx);
+
+  let mut reports: Vec<(usize, DayReport)> = rx.iter().collect();
+  for worker in workers {
+    worker.join().expect("worker thread panicked");
+  }
+  reports.sort_by_key(|(i, _)| *i);
+  reports.into_iter().map(|(_, report)| report).collect()
+}
+
+fn run_day(day: &'static Day, input: String, params: Params, timeout: Duration) -> DayReport {
+  let (tx, rx) = mpsc::channel();
+  thread::spawn(move || {
+    for (i, part) in [day.part1, day.part2].into_iter().enumerate() {
+      let start = Instant::now();
+      if tx.send(Event::Started(i)).is_err() {
+        return;
+      }
+      let result = part(&input, &params);
+      if tx.send(Event::Finished(i, result, start.elapsed())).is_err() {
+        return;
+      }
+    }
+  });
+
+  let not_run = PartReport { outcome: Outcome::NotRun, elapsed: None };
+  let mut report = DayReport { day: day.number, parts: [not_run.clone(), not_run] };
+  let deadline = Instant::now() + timeout;
+  let mut running = None;
+  loop {
+    match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+      Ok(Event::Started(i)) => running = Some((i, Instant::now())),
+      Ok(Event::Finished(i, result, elapsed)) => {
+        running = None;
+        report.parts[i] = PartReport {
+          outcome: match result {
+            Ok(answer) => Outcome::Answer(answer),
+            Err(message) => Outcome::Failed(message),
+          },
+          elapsed: Some(elapsed),
+        };
+      }
+      Err(RecvTimeoutError::Timeout) => {
+        if let Some((i, start)) = running {
+          report.parts[i] = PartReport { outcome: Outcome::TimedOut, elapsed: Some(start.elapsed()) };
+        }
+        break;
+      }
+      // the part's thread is done, or it panicked part way through one.
+      Err(RecvTimeoutError::Disconnected) => {
+        if let Some((i, _)) = running {
+          report.parts[i] = PartReport { outcome: Outcome::Failed("panicked".to_string()), elapsed: None };
+        }
+        break;
+      }
+    }
+  }
+  report
+}
+
+impl DayReport {
+  pub fn is_ok(&self) -> bool {
+    self.parts.iter().all(|p| matches!(p.outcome, Outcome::Answer(_)))
+  }
+
+  pub fn elapsed(&self) -> Duration {
+    self.parts.iter().filter_map(|p| p.elapsed).sum()
+  }
+
+  // a one line summary of how the day went, for progress as days finish.
+  pub fn status(&self) -> String {
+    match self.parts.iter().position(|p| p.outcome == Outcome::TimedOut) {
+      Some(i) => format!("day {}: part {} timed out after {:?}", self.day, i + 1, self.parts[i].elapsed.unwrap_or_default()),
+      None if self.is_ok() => format!("day {}: done in {:?}", self.day, self.elapsed()),
+      None => format!("day {}: failed", self.day),
+    }
+  }
+}
+
+impl fmt::Display for Outcome {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Answer(answer) => write!(f, "{}", answer),
+      Self::Failed(message) => write!(f, "error: {}", message),
+      Self::TimedOut => write!(f, "TIMED OUT"),
+      Self::NotRun => write!(f, "-"),
+    }
+  }
+}
+
+// Lays the reports out as a table, one row per day. Multi-line answers (like day 10's crt)
+// carry on in the rows below.
+pub fn summary(reports: &[DayReport]) -> String {
+  let header = ["day", "part 1", "part 2", "time"].map(|h| h.to_string());
+  let rows: Vec<[String; 4]> = reports
+    .iter()
+    .map(|r| [
+      r.day.to_string(),
+      r.parts[0].outcome.to_string(),
+      r.parts[1].outcome.to_string(),
+      format!("{:.1?}", r.elapsed()),
+    ])
+    .collect();
+
+  let mut widths = [0; 4];
+  for row in std::iter::once(&header).chain(&rows) {
+    for (width, cell) in widths.iter_mut().zip(row) {
+      *width = (*width).max(cell.lines().map(|l| l.chars().count()).max().unwrap_or(0));
+    }
+  }
+
+  let mut table = String::new();
+  for row in std::iter::once(&header).chain(&rows) {
+    let lines: Vec<Vec<&str>> = row.iter().map(|cell| cell.lines().collect()).collect();
+    let height = lines.iter().map(|l| l.len()).max().unwrap_or(0).max(1);
+    for i in 0..height {
+      let cells: Vec<String> = lines
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:width$}", cell.get(i).unwrap_or(&""), width = width))
+        .collect();
+      table.push_str(cells.join("  ").trim_end());
+      table.push('\n');
+    }
+  }
+  table
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use utils::config::Config;
+
+  static DAYS: [Day; 3] = [
+    Day {
+      number: 3,
+      part1: |_, params| params.get("group_size", 2).map(|a: i32| a.to_string()).map_err(|e| e.message),
+      part2: |_, _| Err("oops".to_string()),
+    },
+    Day {
+      number: 1,
+      part1: |input, _| Ok(input.to_string()),
+      part2: |_, _| {
+        thread::sleep(Duration::from_secs(60));
+        Ok("too slow".to_string())
+      },
+    },
+    Day { number: 2, part1: |_, _| panic!("boom"), part2: |_, _| Ok("2".to_string()) },
+  ];
+
+  #[test]
+  pub fn test_reports_in_job_order() {
+    let mut config = Config::default();
+    config.set("day3.group_size=42").unwrap();
+    let mut days: Vec<_> = DAYS.iter().collect();
+    days.sort_by_key(|day| day.number);
+    let jobs = days
+      .into_iter()
+      .map(|day| Job { day, input: "one\ntwo".to_string(), params: config.params(day.number, "input.txt") })
+      .collect();
+    let reports = run(jobs, &Options { jobs: 2, timeout: Duration::from_millis(200) });
+
+    assert_eq!(vec![1, 2, 3], reports.iter().map(|r| r.day).collect::<Vec<_>>());
+    assert_eq!(Outcome::Answer("one\ntwo".to_string()), reports[0].parts[0].outcome);
+    assert_eq!(Outcome::TimedOut, reports[0].parts[1].outcome);
+    assert_eq!("day 1: part 2 timed out after", &reports[0].status()[..29]);
+    assert_eq!(Outcome::Failed("panicked".to_string()), reports[1].parts[0].outcome);
+    assert_eq!(Outcome::NotRun, reports[1].parts[1].outcome);
+    assert_eq!(Outcome::Answer("42".to_string()), reports[2].parts[0].outcome);
+    assert_eq!(Outcome::Failed("oops".to_string()), reports[2].parts[1].outcome);
+  }
+
+  #[test]
+  pub fn test_summary() {
+    let part = |outcome| PartReport { outcome, elapsed: Some(Duration::from_millis(1)) };
+    let reports = vec![
+      DayReport { day: 9, parts: [part(Outcome::Answer("13".to_string())), part(Outcome::TimedOut)] },
+      DayReport { day: 10, parts: [part(Outcome::Answer("1".to_string())), part(Outcome::Answer("#.\n.#".to_string()))] },
+    ];
+    assert_eq!(
+"day  part 1  part 2     time
+9    13      TIMED OUT  2.0ms
+10   1       #.         2.0ms
+             .#
+", summary(&reports));
+  }
+}
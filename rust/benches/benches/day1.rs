@@ -0,0 +1,24 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day1");
+  for (name, input) in benches::inputs(include_str!("../../inputs/day1.txt"), benches::day1(100_000)) {
+    group.bench_with_input(BenchmarkId::new("parse", name), &input, |b, input| {
+      b.iter(|| day1::parse_inventories(input.lines()))
+    });
+    group.bench_with_input(BenchmarkId::new("part1", name), &input, |b, input| {
+      b.iter(|| day1::max_calories(input.lines()))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &input, |b, input| {
+      b.iter(|| day1::max_3_calories(input.lines()))
+    });
+    let inventories = day1::parse_inventories(input.lines()).unwrap();
+    group.bench_with_input(BenchmarkId::new("report", name), &inventories, |b, inventories| {
+      b.iter(|| day1::report::report(inventories, 10))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day10");
+  for (name, input) in benches::inputs(include_str!("../../inputs/day10.txt"), benches::day10(100_000)) {
+    let instructions = day10::parse_instructions(input.lines()).expect("could not parse instructions");
+    group.bench_with_input(BenchmarkId::new("parse", name), &input, |b, input| {
+      b.iter(|| day10::parse_instructions(input.lines()))
+    });
+    group.bench_with_input(BenchmarkId::new("part1", name), &instructions, |b, instructions| {
+      b.iter(|| day10::signal_strength(&day10::register_values(instructions), day10::FIRST_SAMPLE, day10::SAMPLE_INTERVAL))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &instructions, |b, instructions| {
+      b.iter(|| day10::render_crt(&day10::register_values(instructions), day10::CRT_WIDTH, day10::CRT_HEIGHT))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
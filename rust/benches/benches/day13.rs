@@ -0,0 +1,27 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// part1 and part2 take the raw input, so they include parsing.
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day13");
+  for (name, input) in benches::inputs(include_str!("../../inputs/day13.txt"), benches::day13(10_000)) {
+    group.bench_with_input(BenchmarkId::new("parse", name), &input, |b, input| {
+      b.iter(|| {
+        input
+          .lines()
+          .filter(|l| !l.is_empty())
+          .map(day13::parse_packet)
+          .collect::<Result<Vec<_>, _>>()
+      })
+    });
+    group.bench_with_input(BenchmarkId::new("part1", name), &input, |b, input| {
+      b.iter(|| day13::part1(input))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &input, |b, input| {
+      b.iter(|| day13::part2(input))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
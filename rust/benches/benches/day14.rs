@@ -0,0 +1,22 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day14");
+  group.sample_size(10);
+  for (name, input) in benches::inputs(include_str!("../../inputs/day14.txt"), benches::day14(400, 400)) {
+    let paths = day14::parse_input(input.lines()).expect("could not parse rock paths");
+    group.bench_with_input(BenchmarkId::new("parse", name), &input, |b, input| {
+      b.iter(|| day14::parse_input(input.lines()))
+    });
+    group.bench_with_input(BenchmarkId::new("part1", name), &paths, |b, paths| {
+      b.iter(|| day14::min_units_for_infinite_flow(paths, day14::SPIGOT.x))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &paths, |b, paths| {
+      b.iter(|| day14::min_units_until_full(paths, day14::SPIGOT.x))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,28 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// part2 searches up to 4,000,000 rows of the real input, so this takes a while even with
+// the minimum number of samples.
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day15");
+  group.sample_size(10);
+  let inputs = [
+    ("input", include_str!("../../inputs/day15.txt").to_string(), 2_000_000, 4_000_000),
+    ("generated", benches::day15(20_000, 500), 10_000, 20_000),
+  ];
+  for (name, input, row, side) in inputs {
+    let pairs = day15::parse_lines(input.lines()).expect("could not parse sensors");
+    group.bench_with_input(BenchmarkId::new("parse", name), &input, |b, input| {
+      b.iter(|| day15::parse_lines(input.lines()))
+    });
+    group.bench_with_input(BenchmarkId::new("part1", name), &pairs, |b, pairs| {
+      b.iter(|| day15::impossible_positions(pairs, row))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &pairs, |b, pairs| {
+      b.iter(|| day15::find_open_position(pairs, side))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
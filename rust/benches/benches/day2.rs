@@ -0,0 +1,23 @@
+use std::str::FromStr;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day2::Match;
+
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day2");
+  for (name, input) in benches::inputs(include_str!("../../inputs/day2.txt"), benches::day2(100_000)) {
+    group.bench_with_input(BenchmarkId::new("parse", name), &input, |b, input| {
+      b.iter(|| input.lines().map(Match::from_str).collect::<Result<Vec<_>, _>>())
+    });
+    group.bench_with_input(BenchmarkId::new("part1", name), &input, |b, input| {
+      b.iter(|| day2::predict_score_from_move(input.lines()))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &input, |b, input| {
+      b.iter(|| day2::predict_score_from_result(input.lines()))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// day3 works straight off of the lines, so there's no separate parse step to measure.
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day3");
+  for (name, input) in benches::inputs(include_str!("../../inputs/day3.txt"), benches::day3(10_000)) {
+    group.bench_with_input(BenchmarkId::new("part1", name), &input, |b, input| {
+      b.iter(|| day3::score_duplicate_item(input.lines(), day3::Alphabet::Letters))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &input, |b, input| {
+      b.iter(|| day3::score_groups_unique_elements(input, day3::GROUP_SIZE, day3::Alphabet::Letters))
+    });
+    group.bench_with_input(BenchmarkId::new("part1_hashset", name), &input, |b, input| {
+      b.iter(|| hashset_part1(input))
+    });
+    group.bench_with_input(BenchmarkId::new("part2_hashset", name), &input, |b, input| {
+      b.iter(|| hashset_part2(input))
+    });
+  }
+  group.finish();
+}
+
+// The HashSet<char> versions day3 used before ItemSet, to compare against.
+fn hashset_part1(input: &str) -> i32 {
+  input.lines().map(|l| {
+    let items: Vec<_> = l.chars().collect();
+    let left: HashSet<_> = items[..items.len() / 2].iter().collect();
+    let right: HashSet<_> = items[items.len() / 2..].iter().collect();
+    let shared: Vec<_> = left.intersection(&right).copied().collect();
+    day3::score(shared[0]).unwrap()
+  }).sum()
+}
+
+fn hashset_part2(input: &str) -> i32 {
+  input.lines().collect::<Vec<_>>().chunks(3).map(|lines| {
+    let mut shared: HashSet<_> = lines[0].chars().collect();
+    for line in lines {
+      shared = shared.intersection(&line.chars().collect()).copied().collect();
+    }
+    day3::score(shared.iter().next().unwrap()).unwrap()
+  }).sum()
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
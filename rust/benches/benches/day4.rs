@@ -0,0 +1,18 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// day4 parses while it counts, so there's no separate parse step to measure.
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day4");
+  for (name, input) in benches::inputs(include_str!("../../inputs/day4.txt"), benches::day4(100_000)) {
+    group.bench_with_input(BenchmarkId::new("part1", name), &input, |b, input| {
+      b.iter(|| day4::count_fully_contained_pairs(input.lines()))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &input, |b, input| {
+      b.iter(|| day4::count_overlapping_pairs(input.lines()))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
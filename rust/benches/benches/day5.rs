@@ -0,0 +1,21 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// parse covers the stack diagram and the move commands.
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day5");
+  for (name, input) in benches::inputs(include_str!("../../inputs/day5.txt"), benches::day5(1_000, 100_000)) {
+    group.bench_with_input(BenchmarkId::new("parse", name), &input, |b, input| {
+      b.iter(|| day5::parse_input(input))
+    });
+    group.bench_with_input(BenchmarkId::new("part1", name), &input, |b, input| {
+      b.iter(|| day5::top_of_stacks_9000(input))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &input, |b, input| {
+      b.iter(|| day5::top_of_stacks_9001(input))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
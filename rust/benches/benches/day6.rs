@@ -0,0 +1,31 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// day6 works straight off of the signal, so there's no parse step to measure.
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day6");
+  for (name, input) in benches::inputs(include_str!("../../inputs/day6.txt"), benches::day6(1_000_000)) {
+    group.bench_with_input(BenchmarkId::new("part1", name), &input, |b, input| {
+      b.iter(|| day6::first_unique_chars(input, 4))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &input, |b, input| {
+      b.iter(|| day6::first_unique_chars(input, 14))
+    });
+    group.bench_with_input(BenchmarkId::new("part2_try_for_each", name), &input, |b, input| {
+      b.iter(|| day6::first_unique_chars_with_try_for_each(input, 14))
+    });
+    group.bench_with_input(BenchmarkId::new("part2_window", name), &input, |b, input| {
+      b.iter(|| day6::find_marker(input, 14))
+    });
+    // the window doesn't get slower as it gets bigger.
+    group.bench_with_input(BenchmarkId::new("window_100", name), &input, |b, input| {
+      b.iter(|| day6::find_marker(input, 100))
+    });
+    group.bench_with_input(BenchmarkId::new("both_parts_window", name), &input, |b, input| {
+      b.iter(|| day6::find_markers(input, &[4, 14]))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day7");
+  for (name, input) in benches::inputs(include_str!("../../inputs/day7.txt"), benches::day7(10_000, 5)) {
+    let tree = day7::build_tree(&mut input.lines().peekable()).expect("could not parse terminal output");
+    group.bench_with_input(BenchmarkId::new("parse", name), &input, |b, input| {
+      b.iter(|| day7::build_tree(&mut input.lines().peekable()))
+    });
+    group.bench_with_input(BenchmarkId::new("part1", name), &tree, |b, tree| {
+      b.iter(|| day7::part1(tree, day7::MAX_DIR_SIZE))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &tree, |b, tree| {
+      b.iter(|| day7::part2(tree, day7::DISK_SIZE, day7::NEEDED_SPACE))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
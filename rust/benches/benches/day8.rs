@@ -0,0 +1,21 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// part1 and part2 take the raw lines, so they include parsing.
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day8");
+  for (name, input) in benches::inputs(include_str!("../../inputs/day8.txt"), benches::day8(500)) {
+    group.bench_with_input(BenchmarkId::new("parse", name), &input, |b, input| {
+      b.iter(|| day8::parse(input.lines()))
+    });
+    group.bench_with_input(BenchmarkId::new("part1", name), &input, |b, input| {
+      b.iter(|| day8::count_visible_trees(input.lines()))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &input, |b, input| {
+      b.iter(|| day8::highest_scenic_score(input.lines()))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn bench(c: &mut Criterion) {
+  let mut group = c.benchmark_group("day9");
+  for (name, input) in benches::inputs(include_str!("../../inputs/day9.txt"), benches::day9(100_000)) {
+    let commands = day9::parse_commands(input.lines()).expect("could not parse commands");
+    group.bench_with_input(BenchmarkId::new("parse", name), &input, |b, input| {
+      b.iter(|| day9::parse_commands(input.lines()))
+    });
+    group.bench_with_input(BenchmarkId::new("part1", name), &commands, |b, commands| {
+      b.iter(|| day9::simulate_movements(commands, 2))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", name), &commands, |b, commands| {
+      b.iter(|| day9::simulate_movements(commands, 10))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
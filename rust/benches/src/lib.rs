@@ -0,0 +1,261 @@
+// Input generators for the benchmarks in benches/. Each one writes a puzzle input in the same
+// format as the real one for that day, just (much) bigger, and always with a solution so
+// that every part has to do all of its work. The inputs come from utils' seeded Rng, so
+// they're the same on every run.
+
+use std::fmt::Write;
+
+pub use utils::rng::Rng;
+
+// Pairs the real puzzle input with a generated one, named for the benchmark ids.
+pub fn inputs(input: &str, generated: String) -> Vec<(&'static str, String)> {
+  vec![("input", input.to_string()), ("generated", generated)]
+}
+
+pub fn day1(elves: usize) -> String {
+  let mut rng = Rng::new(1);
+  (0..elves)
+    .map(|_| {
+      (0..rng.between(1, 15))
+        .map(|_| rng.between(1000, 9999).to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+    })
+    .collect::<Vec<_>>()
+    .join("\n\n")
+}
+
+pub fn day2(rounds: usize) -> String {
+  let mut rng = Rng::new(2);
+  (0..rounds)
+    .map(|_| format!("{} {}", rng.pick(&["A", "B", "C"]), rng.pick(&["X", "Y", "Z"])))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+// Every rucksack shares exactly one item between its compartments, and every group of three
+// shares exactly one badge: each elf in a group draws the rest of its items from its own
+// pool, with separate halves of the pool for each compartment.
+pub fn day3(groups: usize) -> String {
+  let mut rng = Rng::new(3);
+  let mut letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+  let mut lines = vec![];
+  for _ in 0..groups {
+    rng.shuffle(&mut letters);
+    let badge = letters[0];
+    for elf in 0..3 {
+      let duplicate = letters[1 + elf];
+      let pool = &letters[4 + elf * 16..4 + (elf + 1) * 16];
+      let half = rng.between(8, 16) as usize;
+      let mut left: Vec<char> = vec![duplicate, badge];
+      left.extend((2..half).map(|_| *rng.pick(&pool[..8])));
+      let mut right: Vec<char> = vec![duplicate];
+      right.extend((1..half).map(|_| *rng.pick(&pool[8..])));
+      rng.shuffle(&mut left);
+      rng.shuffle(&mut right);
+      lines.push(left.into_iter().chain(right).collect::<String>());
+    }
+  }
+  lines.join("\n")
+}
+
+pub fn day4(pairs: usize) -> String {
+  let mut rng = Rng::new(4);
+  let range = |rng: &mut Rng| {
+    let lower = rng.between(1, 99);
+    format!("{}-{}", lower, rng.between(lower, 99))
+  };
+  (0..pairs)
+    .map(|_| format!("{},{}", range(&mut rng), range(&mut rng)))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+// Moves only ever take as many crates as the source stack holds.
+pub fn day5(crates_per_stack: usize, moves: usize) -> String {
+  let mut rng = Rng::new(5);
+  let n_stacks = 9;
+  let mut heights = vec![crates_per_stack; n_stacks];
+
+  let mut content = String::new();
+  for _ in 0..crates_per_stack {
+    let row: Vec<_> = (0..n_stacks)
+      .map(|_| format!("[{}]", (b'A' + rng.between(0, 25) as u8) as char))
+      .collect();
+    writeln!(content, "{}", row.join(" ")).unwrap();
+  }
+  let labels: Vec<_> = (1..=n_stacks).map(|i| format!(" {} ", i)).collect();
+  writeln!(content, "{}\n", labels.join(" ")).unwrap();
+
+  for _ in 0..moves {
+    let source = loop {
+      let source = rng.between(0, n_stacks as i64 - 1) as usize;
+      if heights[source] > 0 {
+        break source;
+      }
+    };
+    let destination = (source + rng.between(1, n_stacks as i64 - 1) as usize) % n_stacks;
+    let amount = rng.between(1, heights[source].min(20) as i64) as usize;
+    heights[source] -= amount;
+    heights[destination] += amount;
+    writeln!(content, "move {} from {} to {}", amount, source + 1, destination + 1).unwrap();
+  }
+  content
+}
+
+// Only three distinct characters until the very end, so both markers are found in the
+// last few characters.
+pub fn day6(length: usize) -> String {
+  let mut rng = Rng::new(6);
+  let mut signal: String = (0..length).map(|_| *rng.pick(&['a', 'b', 'c'])).collect();
+  signal.push_str("abcdefghijklmnop");
+  signal
+}
+
+// Files add up to roughly 50,000,000 so that part 2 has something to delete.
+pub fn day7(dirs: usize, files_per_dir: usize) -> String {
+  let mut rng = Rng::new(7);
+  let mut children: Vec<Vec<usize>> = vec![vec![]];
+  for dir in 1..dirs {
+    let parent = rng.between(0, dir as i64 - 1) as usize;
+    children[parent].push(dir);
+    children.push(vec![]);
+  }
+
+  let average_size = (50_000_000 / (dirs * files_per_dir)).max(1) as i64;
+  let mut content = String::from("$ cd /\n");
+  let mut stack = vec![(0, false)];
+  while let Some((dir, visited)) = stack.pop() {
+    if visited {
+      content.push_str("$ cd ..\n");
+      continue;
+    }
+    if dir != 0 {
+      writeln!(content, "$ cd d{}", dir).unwrap();
+    }
+    content.push_str("$ ls\n");
+    for child in &children[dir] {
+      writeln!(content, "dir d{}", child).unwrap();
+    }
+    for file in 0..files_per_dir {
+      writeln!(content, "{} f{}.txt", rng.between(1, 2 * average_size), file).unwrap();
+    }
+    if dir != 0 {
+      stack.push((dir, true));
+    }
+    stack.extend(children[dir].iter().rev().map(|child| (*child, false)));
+  }
+  content
+}
+
+pub fn day8(size: usize) -> String {
+  let mut rng = Rng::new(8);
+  (0..size)
+    .map(|_| (0..size).map(|_| char::from(b'0' + rng.between(0, 9) as u8)).collect::<String>())
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+pub fn day9(commands: usize) -> String {
+  let mut rng = Rng::new(9);
+  (0..commands)
+    .map(|_| format!("{} {}", rng.pick(&["U", "D", "L", "R"]), rng.between(1, 20)))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+pub fn day10(instructions: usize) -> String {
+  let mut rng = Rng::new(10);
+  (0..instructions)
+    .map(|_| match rng.between(0, 2) {
+      0 => "noop".to_string(),
+      _ => format!("addx {}", rng.between(-5, 5)),
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+pub fn day13(pairs: usize) -> String {
+  fn packet(rng: &mut Rng, depth: usize) -> String {
+    let items: Vec<_> = (0..rng.between(0, 5))
+      .map(|_| {
+        if depth < 4 && rng.between(0, 2) == 0 {
+          packet(rng, depth + 1)
+        } else {
+          rng.between(0, 10).to_string()
+        }
+      })
+      .collect();
+    format!("[{}]", items.join(","))
+  }
+
+  let mut rng = Rng::new(13);
+  (0..pairs)
+    .map(|_| format!("{}\n{}", packet(&mut rng, 0), packet(&mut rng, 0)))
+    .collect::<Vec<_>>()
+    .join("\n\n")
+}
+
+// Rock shelves and walls scattered under the spigot at x=500, down to the given depth.
+pub fn day14(depth: usize, paths: usize) -> String {
+  let mut rng = Rng::new(14);
+  let depth = depth as i64;
+  (0..paths)
+    .map(|_| {
+      let (mut x, mut y) = (rng.between(500 - depth, 500 + depth), rng.between(5, depth));
+      let mut points = vec![format!("{},{}", x, y)];
+      for segment in 0..rng.between(1, 4) {
+        if segment % 2 == 0 {
+          x = rng.between(x - 10, x + 10);
+        } else {
+          y = rng.between(5.max(y - 10), depth.min(y + 10));
+        }
+        points.push(format!("{},{}", x, y));
+      }
+      points.join(" -> ")
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+// Sensors on a lattice, each reaching exactly to its neighbours, so that every position in
+// [0, side] is covered and part 2 has to search every row.
+pub fn day15(side: i64, spacing: i64) -> String {
+  let mut lines = vec![];
+  for y in (0..=side + spacing).step_by(spacing as usize) {
+    for x in (0..=side + spacing).step_by(spacing as usize) {
+      lines.push(format!(
+        "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+        x, y, x + spacing, y
+      ));
+    }
+  }
+  lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_generated_inputs_are_solvable() {
+    assert!(day1::max_3_calories(day1(100).lines()).is_ok());
+    assert!(day2::predict_score_from_result(day2(100).lines()).is_ok());
+    assert!(day3::score_duplicate_item(day3(100).lines(), day3::Alphabet::Letters).is_ok());
+    assert!(day3::score_groups_unique_elements(&day3(100), day3::GROUP_SIZE, day3::Alphabet::Letters).is_ok());
+    assert!(day4::count_overlapping_pairs(day4(100).lines()).is_ok());
+    assert!(day5::top_of_stacks_9001(&day5(10, 100)).is_ok());
+    assert_eq!(1_000 + 14, day6::first_unique_chars(&day6(1_000), 14));
+    assert_eq!(Some(1_000 + 14), day6::find_marker(&day6(1_000), 14));
+    let tree = day7::build_tree(&mut day7(100, 5).lines().peekable()).unwrap();
+    assert!(day7::part2(&tree, day7::DISK_SIZE, day7::NEEDED_SPACE).is_ok());
+    assert!(day8::highest_scenic_score(day8(10).lines()).is_ok());
+    assert!(day9::parse_commands(day9(100).lines()).is_ok());
+    assert!(day10::parse_instructions(day10(100).lines()).is_ok());
+    assert!(day13::part2(&day13(100)).is_ok());
+    let paths = day14::parse_input(day14(50, 20).lines()).unwrap();
+    assert!(day14::min_units_until_full(&paths, day14::SPIGOT.x).is_ok());
+    let pairs = day15::parse_lines(day15(100, 10).lines()).unwrap();
+    assert_eq!(None, day15::find_open_position(&pairs, 100));
+  }
+}
@@ -0,0 +1,177 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::str::Lines;
+
+pub mod report;
+
+// Everything one elf is carrying.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inventory {
+    pub elf: usize,
+    pub items: Vec<u64>,
+}
+
+impl Inventory {
+    // Can't overflow for inventories from parse_inventories.
+    pub fn total(&self) -> u64 {
+        self.items.iter().sum()
+    }
+}
+
+pub fn max_calories(lines: Lines<'_>) -> Result<u64, ParseCaloriesError> {
+    let inventories = parse_inventories(lines)?;
+    Ok(top_n(&inventories, 1).first().map_or(0, |(_, total)| *total))
+}
+
+pub fn max_3_calories(lines: Lines<'_>) -> Result<Vec<u64>, ParseCaloriesError> {
+    let inventories = parse_inventories(lines)?;
+    Ok(top_n(&inventories, 3).into_iter().map(|(_, total)| total).collect())
+}
+
+// Splits the input into each elf's items. Every elf's total is checked to fit in a u64,
+// so they can be summed freely afterwards.
+//
+// Elves are separated by blank lines, where a line of only whitespace counts as blank, and
+// several blank lines in a row are the same as one. Blank lines at the start or end don't
+// add empty elves.
+pub fn parse_inventories(lines: Lines<'_>) -> Result<Vec<Inventory>, ParseCaloriesError> {
+    let mut inventories: Vec<Inventory> = vec![];
+    let mut in_elf = false;
+    let mut total: u64 = 0;
+    for (i, line) in lines.enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            in_elf = false;
+            continue;
+        }
+        if !in_elf {
+            inventories.push(Inventory { elf: inventories.len(), items: vec![] });
+            in_elf = true;
+            total = 0;
+        }
+        let calories = line.parse::<u64>().map_err(|_| ParseCaloriesError::InvalidCalories {
+            line: i + 1,
+            s: line.to_string(),
+        })?;
+        total = total.checked_add(calories).ok_or(ParseCaloriesError::Overflow {
+            line: i + 1,
+            elf: inventories.len() - 1,
+        })?;
+        inventories.last_mut().unwrap().items.push(calories);
+    }
+    Ok(inventories)
+}
+
+// The n elves carrying the most calories, as (elf index, total), most first. Ties go to the
+// elf that comes first.
+//
+// Keeps only the best n seen so far in a min-heap, so that finding the top few of a huge
+// number of elves is O(elves * log n) rather than sorting all of them.
+pub fn top_n(inventories: &[Inventory], n: usize) -> Vec<(usize, u64)> {
+    if n == 0 {
+        return vec![];
+    }
+    let mut heap = BinaryHeap::with_capacity(n + 1);
+    for inventory in inventories {
+        // the heap's top is the worst elf kept: the lowest total, then the latest index.
+        heap.push(Reverse((inventory.total(), Reverse(inventory.elf))));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec().into_iter().map(|Reverse((total, Reverse(index)))| (index, total)).collect()
+}
+
+// Lines are counted from 1, and elves from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCaloriesError {
+    InvalidCalories { line: usize, s: String },
+    Overflow { line: usize, elf: usize },
+}
+
+impl fmt::Display for ParseCaloriesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCalories { line, s } => write!(f, "line {}: not a valid calorie count: {}", line, s),
+            Self::Overflow { line, elf } => write!(f, "line {}: total calories for elf {} overflowed", line, elf),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_calories() {
+        let input = include_str!("../../inputs/day1.sample.txt");
+        assert_eq!(Ok(24000), max_calories(input.lines()));
+        assert_eq!(Ok(vec![24000, 11000, 10000]), max_3_calories(input.lines()));
+    }
+
+    #[test]
+    fn test_top_n() {
+        let inventories = parse_inventories(include_str!("../../inputs/day1.sample.txt").lines()).unwrap();
+        assert_eq!(5, inventories.len());
+        assert_eq!(Inventory { elf: 3, items: vec![7000, 8000, 9000] }, inventories[3]);
+        assert_eq!(vec![(3, 24000)], top_n(&inventories, 1));
+        assert_eq!(vec![(3, 24000), (2, 11000), (4, 10000)], top_n(&inventories, 3));
+        assert_eq!(5, top_n(&inventories, 10).len());
+        assert!(top_n(&inventories, 0).is_empty());
+        // ties keep the first elf
+        let inventories = parse_inventories("1\n\n2\n\n1\n1\n\n2".lines()).unwrap();
+        assert_eq!(vec![(1, 2), (2, 2)], top_n(&inventories, 2));
+    }
+
+    #[test]
+    fn test_invalid_calories() {
+        assert_eq!(
+            Err(ParseCaloriesError::InvalidCalories { line: 2, s: "abc".to_string() }),
+            max_calories("100\nabc".lines())
+        );
+        assert_eq!(
+            Err(ParseCaloriesError::InvalidCalories { line: 1, s: "-5".to_string() }),
+            max_calories("-5".lines())
+        );
+        assert_eq!(
+            Err(ParseCaloriesError::InvalidCalories { line: 3, s: "1 2".to_string() }),
+            max_calories("1\n\n1 2".lines())
+        );
+        assert_eq!(
+            "line 2: not a valid calorie count: 1x",
+            max_calories("1\n1x".lines()).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        // totals past i32 are fine
+        assert_eq!(Ok(4294967294), max_calories("2147483647\n2147483647".lines()));
+        assert_eq!(
+            Err(ParseCaloriesError::Overflow { line: 4, elf: 1 }),
+            max_calories("1\n\n18446744073709551615\n1".lines())
+        );
+        assert!(max_calories("18446744073709551616".lines()).is_err());
+        // elves are summed separately, so each only has to fit on its own
+        assert_eq!(Ok(18446744073709551615), max_calories("18446744073709551615\n\n18446744073709551615".lines()));
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        assert_eq!(Ok(vec![5]), max_3_calories("5".lines()));
+        assert_eq!(Ok(0), max_calories("".lines()));
+        assert!(parse_inventories("\n \n\t".lines()).unwrap().is_empty());
+
+        // runs of blank lines, whitespace-only lines, and blank lines at either end all just
+        // separate elves
+        let expected = vec![
+            Inventory { elf: 0, items: vec![1, 2] },
+            Inventory { elf: 1, items: vec![3] },
+            Inventory { elf: 2, items: vec![4] },
+        ];
+        assert_eq!(Ok(expected.clone()), parse_inventories("1\n2\n\n3\n\n4".lines()));
+        assert_eq!(Ok(expected.clone()), parse_inventories("\n\n1\n2\n\n\n\n3\n  \n4\n\n".lines()));
+        assert_eq!(Ok(expected), parse_inventories(" 1 \n2\t\n\t\n3\r\n\r\n4".lines()));
+    }
+}
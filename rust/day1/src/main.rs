@@ -1,54 +1,36 @@
-use std::str::Lines;
+use day1::report::{inventories_csv, report};
+use day1::{max_3_calories, max_calories, parse_inventories};
 use utils::cli::Cli;
 
+// the number of histogram buckets in a report.
+const BUCKETS: usize = 10;
+
 fn main() {
     let args = Cli::parse();
     let content = std::fs::read_to_string(&args.path).expect("could not read file");
-    max_calories(content.lines());
-    max_3_calories(content.lines());
-}
-
-fn max_calories(lines: Lines<'_>) {
-    let mut calories = Vec::new();
-    let mut cur = 0;
-    for line in lines {
-        if line.is_empty() {
-            calories.push(cur);
-            cur = 0;
-            continue;
-        }
-        let calories = line.parse::<i32>().unwrap();
-        cur += calories;
+    match args.args.first().map(|a| a.as_str()) {
+        Some(mode @ ("report" | "report-csv" | "csv")) => match parse_inventories(content.lines()) {
+            Ok(inventories) if mode == "csv" => print!("{}", inventories_csv(&inventories)),
+            Ok(inventories) if mode == "report-csv" => print!("{}", report(&inventories, BUCKETS).to_csv()),
+            Ok(inventories) => print!("{}", report(&inventories, BUCKETS)),
+            Err(e) => println!("Error could not parse inventories: {}", e),
+        },
+        Some(mode) => println!("unknown mode {}, expected report, report-csv or csv", mode),
+        None => solve(&content),
     }
-    calories.push(cur);
-    calories.sort_by(|a, b| b.cmp(a));
-    let max = calories[0];
-    println!("elf with max calories: {}", max);
 }
 
-// If the number of elves were large, we can reduce the runtime from n*log n => lg n
-// by using a BinaryHeap instead of sorting.
-fn max_3_calories(lines: Lines<'_>) {
-    let mut calories = Vec::new();
-    let mut cur = 0;
-    for line in lines {
-        if line.is_empty() {
-            calories.push(cur);
-            cur = 0;
-            continue;
-        }
-        let calories = line.parse::<i32>().unwrap();
-        cur += calories;
+fn solve(content: &str) {
+    match max_calories(content.lines()) {
+        Ok(max) => println!("elf with max calories: {}", max),
+        Err(e) => println!("Error could not calculate: max calories: {}", e),
+    }
+    match max_3_calories(content.lines()) {
+        Ok(top3) => println!(
+            "elves with top3 calories: {} ({})",
+            top3.iter().map(|c| *c as u128).sum::<u128>(),
+            top3.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" ")
+        ),
+        Err(e) => println!("Error could not calculate: top3 calories: {}", e),
     }
-    calories.push(cur);
-    calories.sort_by(|a, b| b.cmp(a));
-    let max = calories[0];
-    println!("elf with max calories: {}", max);
-    println!(
-        "elves with top3 calories: {} ({} {} {})",
-        calories[0] + calories[1] + calories[2],
-        calories[0],
-        calories[1],
-        calories[2]
-    );
 }
@@ -0,0 +1,207 @@
+use std::fmt;
+use std::fmt::Write;
+
+use crate::Inventory;
+
+// The percentiles of elves' totals included in a report, besides the median.
+pub const PERCENTILES: [u32; 5] = [10, 25, 75, 90, 99];
+
+// How many of the elves carrying the most items are listed.
+const MOST_ITEMS: usize = 3;
+
+// Statistics about the elves' inventories.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub elves: usize,
+    pub items: usize,
+    pub mean: f64,
+    pub median: f64,
+    // (percentile, total)
+    pub percentiles: Vec<(u32, u64)>,
+    pub histogram: Vec<Bucket>,
+    // (elf, number of items)
+    pub most_items: Vec<(usize, usize)>,
+}
+
+// The number of elves whose totals are in [first, last].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bucket {
+    pub first: u64,
+    pub last: u64,
+    pub elves: usize,
+}
+
+pub fn report(inventories: &[Inventory], buckets: usize) -> Report {
+    let mut totals: Vec<u64> = inventories.iter().map(|i| i.total()).collect();
+    totals.sort_unstable();
+
+    // summed as u128, since only each elf's total is known to fit in a u64.
+    let mean = if totals.is_empty() {
+        0.0
+    } else {
+        totals.iter().map(|t| *t as u128).sum::<u128>() as f64 / totals.len() as f64
+    };
+    let median = match totals.len() {
+        0 => 0.0,
+        n if n % 2 == 0 => (totals[n / 2 - 1] as u128 + totals[n / 2] as u128) as f64 / 2.0,
+        n => totals[n / 2] as f64,
+    };
+
+    let mut most_items: Vec<_> = inventories.iter().map(|i| (i.elf, i.items.len())).collect();
+    most_items.sort_by_key(|(elf, items)| (std::cmp::Reverse(*items), *elf));
+    most_items.truncate(MOST_ITEMS);
+
+    Report {
+        elves: inventories.len(),
+        items: inventories.iter().map(|i| i.items.len()).sum(),
+        mean,
+        median,
+        percentiles: PERCENTILES.iter().filter_map(|p| Some((*p, percentile(&totals, *p)?))).collect(),
+        histogram: histogram(&totals, buckets),
+        most_items,
+    }
+}
+
+// Nearest rank: the smallest total that at least p% of elves are at or below.
+fn percentile(sorted: &[u64], p: u32) -> Option<u64> {
+    let rank = (p as usize * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+// Splits the totals' range into (at most) `buckets` buckets of the same width.
+//
+// Works in u128, since the span of totals might not fit in a u64.
+fn histogram(sorted: &[u64], buckets: usize) -> Vec<Bucket> {
+    let (Some(min), Some(max)) = (sorted.first(), sorted.last()) else {
+        return vec![];
+    };
+    let (min, max) = (*min as u128, *max as u128);
+    let span = max - min + 1;
+    let width = span.div_ceil(buckets.max(1) as u128);
+    let mut histogram: Vec<Bucket> = (0..span.div_ceil(width))
+        .map(|i| Bucket {
+            first: (min + i * width) as u64,
+            last: (min + (i + 1) * width - 1).min(max) as u64,
+            elves: 0,
+        })
+        .collect();
+    for total in sorted {
+        histogram[((*total as u128 - min) / width) as usize].elves += 1;
+    }
+    histogram
+}
+
+impl Report {
+    // One `section,key,value` row per statistic.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("section,key,value\n");
+        writeln!(csv, "summary,elves,{}", self.elves).unwrap();
+        writeln!(csv, "summary,items,{}", self.items).unwrap();
+        writeln!(csv, "summary,mean,{}", self.mean).unwrap();
+        writeln!(csv, "summary,median,{}", self.median).unwrap();
+        for (p, total) in &self.percentiles {
+            writeln!(csv, "percentile,{},{}", p, total).unwrap();
+        }
+        for bucket in &self.histogram {
+            writeln!(csv, "histogram,{}-{},{}", bucket.first, bucket.last, bucket.elves).unwrap();
+        }
+        for (elf, items) in &self.most_items {
+            writeln!(csv, "most_items,{},{}", elf, items).unwrap();
+        }
+        csv
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "elves: {}", self.elves)?;
+        writeln!(f, "items: {}", self.items)?;
+        writeln!(f, "mean: {:.1}", self.mean)?;
+        writeln!(f, "median: {:.1}", self.median)?;
+        for (p, total) in &self.percentiles {
+            writeln!(f, "p{}: {}", p, total)?;
+        }
+        writeln!(f, "histogram:")?;
+        let most = self.histogram.iter().map(|b| b.elves).max().unwrap_or(0).max(1);
+        for bucket in &self.histogram {
+            let bar = "#".repeat((bucket.elves * 50).div_ceil(most));
+            writeln!(f, "  {:>8}-{:<8} {:>6} {}", bucket.first, bucket.last, bucket.elves, bar)?;
+        }
+        writeln!(f, "most items:")?;
+        for (elf, items) in &self.most_items {
+            writeln!(f, "  elf {}: {} items", elf, items)?;
+        }
+        Ok(())
+    }
+}
+
+// Every item as an `elf,item,calories` row, in the order they were listed.
+pub fn inventories_csv(inventories: &[Inventory]) -> String {
+    let mut csv = String::from("elf,item,calories\n");
+    for inventory in inventories {
+        for (item, calories) in inventory.items.iter().enumerate() {
+            writeln!(csv, "{},{},{}", inventory.elf, item, calories).unwrap();
+        }
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_inventories;
+
+    #[test]
+    fn test_report() {
+        let inventories = parse_inventories(include_str!("../../inputs/day1.sample.txt").lines()).unwrap();
+        let report = report(&inventories, 4);
+        assert_eq!(5, report.elves);
+        assert_eq!(10, report.items);
+        assert_eq!(11000.0, report.mean);
+        assert_eq!(10000.0, report.median);
+        assert_eq!(vec![(10, 4000), (25, 6000), (75, 11000), (90, 24000), (99, 24000)], report.percentiles);
+        assert_eq!(
+            vec![
+                Bucket { first: 4000, last: 9000, elves: 2 },
+                Bucket { first: 9001, last: 14001, elves: 2 },
+                Bucket { first: 14002, last: 19002, elves: 0 },
+                Bucket { first: 19003, last: 24000, elves: 1 },
+            ],
+            report.histogram
+        );
+        assert_eq!(vec![(0, 3), (3, 3), (2, 2)], report.most_items);
+        assert!(report.to_csv().starts_with("section,key,value\nsummary,elves,5\n"));
+        assert!(report.to_csv().ends_with("most_items,0,3\nmost_items,3,3\nmost_items,2,2\n"));
+    }
+
+    #[test]
+    fn test_small_reports() {
+        let report = report(&[], 4);
+        assert_eq!((0, 0.0, 0.0), (report.elves, report.mean, report.median));
+        assert!(report.percentiles.is_empty() && report.histogram.is_empty());
+
+        let inventories = parse_inventories("5\n\n5\n\n7".lines()).unwrap();
+        let report = super::report(&inventories, 10);
+        assert_eq!(5.0, report.median);
+        assert_eq!(3, report.histogram.len());
+        assert_eq!(Bucket { first: 7, last: 7, elves: 1 }, report.histogram[2]);
+
+        // totals at both ends of a u64
+        let inventories = parse_inventories("0\n\n18446744073709551615\n\n18446744073709551615".lines()).unwrap();
+        let report = super::report(&inventories, 2);
+        assert_eq!(18446744073709551615.0 * 2.0 / 3.0, report.mean);
+        assert_eq!(
+            vec![
+                Bucket { first: 0, last: 9223372036854775807, elves: 1 },
+                Bucket { first: 9223372036854775808, last: 18446744073709551615, elves: 2 },
+            ],
+            report.histogram
+        );
+    }
+
+    #[test]
+    fn test_inventories_csv() {
+        let inventories = parse_inventories("1\n2\n\n3".lines()).unwrap();
+        assert_eq!("elf,item,calories\n0,0,1\n0,1,2\n1,0,3\n", inventories_csv(&inventories));
+    }
+}
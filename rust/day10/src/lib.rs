@@ -0,0 +1,123 @@
+use std::str::{FromStr, Lines};
+
+use color_eyre::eyre::{eyre, Context};
+
+pub const CRT_WIDTH: usize = 40;
+pub const CRT_HEIGHT: usize = 6;
+// the cycles part1 samples the signal strength during.
+pub const FIRST_SAMPLE: usize = 20;
+pub const SAMPLE_INTERVAL: usize = 40;
+
+pub fn parse_instructions(lines: Lines<'_>) -> color_eyre::Result<Vec<Instruction>> {
+  lines
+    .enumerate()
+    .map(|(i, l)| Instruction::from_str(l).wrap_err_with(|| format!("on line {}", i + 1)))
+    .collect()
+}
+
+// Runs the program, returning the value of the x register *during* each cycle.
+pub fn register_values(instructions: &[Instruction]) -> Vec<i64> {
+  let mut x_register: i64 = 1;
+  let mut x_values = vec![];
+  for instruction in instructions {
+    for _ in 0..instruction.cycle_length() {
+      x_values.push(x_register);
+    }
+    match instruction {
+      Instruction::Noop => (),
+      Instruction::AddX(amount) => x_register += *amount as i64,
+    }
+  }
+  x_values
+}
+
+// Sums the signal strength during the `first` cycle, and every `interval` cycles after that.
+// An interval of 0 only samples the first.
+pub fn signal_strength(x_values: &[i64], first: usize, interval: usize) -> i64 {
+  x_values
+    .iter()
+    .enumerate()
+    .map(|(i, x)| (i + 1, x))
+    .filter(|(cycle, _)| *cycle >= first && (*cycle - first).checked_rem(interval).unwrap_or(*cycle - first) == 0)
+    .map(|(cycle, x)| x * cycle as i64)
+    .sum()
+}
+
+// Draws a pixel for each cycle the 3 pixel wide sprite (centered at x) covers the
+// crt position. Cycles after the last line of the crt are not drawn.
+pub fn render_crt(x_values: &[i64], width: usize, height: usize) -> Vec<String> {
+  if width == 0 {
+    return vec![String::new(); height];
+  }
+  let mut crt: Vec<char> = vec!['.'; width * height];
+  for (crt_pos, x_register) in x_values.iter().take(crt.len()).enumerate() {
+    let x_pos = (crt_pos % width) as i64;
+    if ((x_register - 1)..(x_register + 2)).contains(&x_pos) {
+      crt[crt_pos] = '#';
+    }
+  }
+  crt
+    .chunks(width)
+    .map(|crt_line| crt_line.iter().collect::<String>())
+    .collect()
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Instruction {
+  Noop,
+  AddX(i32),
+}
+
+impl Instruction {
+  pub fn cycle_length(&self) -> i32 {
+    match self {
+      Self::Noop => 1,
+      Self::AddX(_) => 2,
+    }
+  }
+}
+
+impl FromStr for Instruction {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+      match s.split_once(' ') {
+        Some(("addx", a)) => Ok(Self::AddX(a.parse::<i32>().wrap_err_with(|| format!("not a valid amount: {}", a))?)),
+        _ if s == "noop" => Ok(Self::Noop),
+        _ => Err(eyre!("can't understand instruction: {}", s)),
+      }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_small_program() {
+    let instructions = parse_instructions(include_str!("../../inputs/day10.sample2.txt").lines()).unwrap();
+    assert_eq!(vec![1, 1, 1, 4, 4], register_values(&instructions));
+  }
+
+  #[test]
+  pub fn test_signal_strength() {
+    let instructions = parse_instructions(include_str!("../../inputs/day10.sample1.txt").lines()).unwrap();
+    let x_values = register_values(&instructions);
+    assert_eq!(13140, signal_strength(&x_values, FIRST_SAMPLE, SAMPLE_INTERVAL));
+    assert_eq!("##..##..##..##..##..##..##..##..##..##..", render_crt(&x_values, CRT_WIDTH, CRT_HEIGHT)[0]);
+    // a smaller crt, sampled less often
+    assert_eq!(420, signal_strength(&x_values, 20, 0));
+    assert_eq!(vec!["##..#", "....."], render_crt(&x_values, 5, 2));
+  }
+
+  #[test]
+  pub fn test_malformed_program() {
+    assert!(parse_instructions("addx".lines()).is_err());
+    assert!(parse_instructions("addx one".lines()).is_err());
+    assert!(parse_instructions("mul 2".lines()).is_err());
+    // programs that run past the end of the crt are not drawn
+    let instructions = vec![Instruction::Noop; 300];
+    assert_eq!(CRT_HEIGHT, render_crt(&register_values(&instructions), CRT_WIDTH, CRT_HEIGHT).len());
+    assert_eq!(vec![""; 3], render_crt(&register_values(&instructions), 0, 3));
+  }
+}
@@ -0,0 +1,249 @@
+use std::cmp::{min, max};
+use std::fmt;
+use std::iter::FromIterator;
+use std::str::{Lines};
+use nom::IResult;
+use nom::bytes::complete::{tag, take_while1};
+use nom::combinator::{map_res, opt};
+use nom::multi::many1;
+use nom::sequence::tuple;
+
+// Where the sand pours in from, unless told otherwise. The spigot is always at the top of
+// the scan, but can be moved sideways.
+pub const SPIGOT: Point = Point { x: 500, y: 0 };
+
+// Sand comes to rest in a pyramid under the spigot, so the work grows with the cube of the
+// cave's depth. Anything deeper than this is almost certainly not a real scan.
+pub const MAX_DEPTH: usize = 1_000;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Point {
+  pub x: usize,
+  pub y: usize,
+}
+
+// A scan of the cave. Sand can only ever drift one column sideways for each row it falls,
+// so the screen only needs to cover the triangle below the spigot; rocks outside of it are
+// never touched and are left off.
+pub struct Cave {
+  screen: Vec<Vec<char>>,
+  spigot_x: usize,
+  // screen column of the spigot. also the number of columns on either side of it.
+  spigot_column: usize,
+  has_floor: bool,
+}
+
+pub fn min_units_for_infinite_flow(paths: &[Vec<Point>], spigot_x: usize) -> Result<(usize, Cave), ParsePathError> {
+  let mut cave = Cave::new(paths, spigot_x, false)?;
+
+  // simulate falling sand
+  let mut sand_units = 0;
+  while let Some((x, y)) = cave.drop_sand() {
+    cave.screen[y][x] = 'o';
+    sand_units += 1;
+  }
+  Ok((sand_units, cave))
+}
+
+pub fn min_units_until_full(paths: &[Vec<Point>], spigot_x: usize) -> Result<(usize, Cave), ParsePathError> {
+  let mut cave = Cave::new(paths, spigot_x, true)?;
+
+  // simulate falling sand
+  let mut sand_units = 0;
+  while let Some((x, y)) = cave.drop_sand() {
+    cave.screen[y][x] = 'o';
+    sand_units += 1;
+    if y == SPIGOT.y {
+      break;
+    }
+  }
+  Ok((sand_units, cave))
+}
+
+impl Cave {
+  fn new(paths: &[Vec<Point>], spigot_x: usize, has_floor: bool) -> Result<Cave, ParsePathError> {
+    let (_, bottom_right) = get_bounds(paths);
+    if bottom_right.y > MAX_DEPTH {
+      return Err(ParsePathError {
+        message: format!("the cave is {} deep, but at most {} is supported", bottom_right.y, MAX_DEPTH)
+      });
+    }
+
+    let floor = bottom_right.y + 2;
+    let spigot_column = floor + 1;
+    let mut screen = vec![vec!['.'; 2 * spigot_column + 1]; if has_floor { floor + 1 } else { bottom_right.y + 1 }];
+
+    // Draw spigot to on screen
+    screen[SPIGOT.y][spigot_column] = '+';
+    if has_floor {
+      screen[floor].iter_mut().for_each(|c| *c = '#');
+    }
+
+    let mut cave = Cave { screen, spigot_x, spigot_column, has_floor };
+    cave.draw_paths(paths);
+    Ok(cave)
+  }
+
+  // Follows a unit of sand from the spigot, returning the screen position where it comes to
+  // rest, or None if it falls out of the bottom of the screen.
+  fn drop_sand(&self) -> Option<(usize, usize)> {
+    let (mut x, mut y) = (self.spigot_column, SPIGOT.y);
+    if is_blocked(self.screen[y][x]) {
+      return None;
+    }
+    while y + 1 < self.screen.len() {
+      if !is_blocked(self.screen[y+1][x]) {
+        // fall straight down
+      } else if !is_blocked(self.screen[y+1][x-1]) {
+        x -= 1;
+      } else if !is_blocked(self.screen[y+1][x+1]) {
+        x += 1;
+      } else {
+        return Some((x, y));
+      }
+      y += 1;
+    }
+    None
+  }
+
+  fn draw_paths(&mut self, paths: &[Vec<Point>]) {
+    // the x coordinate of the first screen column, which may be left of 0.
+    let left = self.spigot_x as i64 - self.spigot_column as i64;
+    let right = left + self.screen[0].len() as i64 - 1;
+    for path in paths {
+      let mut start = &path[0];
+      for point in &path[1..] {
+        let (start_y, stop_y) = get_range(start.y, point.y);
+        let (start_x, stop_x) = get_range(start.x, point.x);
+        let (start_x, stop_x) = (max(start_x as i64, left), min(stop_x as i64, right + 1));
+        for row in self.screen.iter_mut().take(stop_y).skip(start_y) {
+          for x in start_x..stop_x {
+            row[(x - left) as usize] = '#';
+          }
+        }
+        start = point;
+      }
+    }
+  }
+}
+
+impl fmt::Display for Cave {
+  // the screen can get wide, so only show the area sand or rocks are in.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let rows = if self.has_floor { &self.screen[..self.screen.len() - 1] } else { &self.screen[..] };
+    let used_columns: Vec<_> = rows
+      .iter()
+      .flat_map(|row| row.iter().enumerate().filter(|(_, c)| **c != '.').map(|(x, _)| x))
+      .collect();
+    let first = used_columns.iter().min().unwrap_or(&self.spigot_column).saturating_sub(1);
+    let last = min(used_columns.iter().max().unwrap_or(&self.spigot_column) + 2, self.screen[0].len());
+    for (line, row) in self.screen.iter().enumerate() {
+      writeln!(f, "{:2}: {}", line, String::from_iter(&row[first..last]))?;
+    }
+    Ok(())
+  }
+}
+
+fn is_blocked(c: char) -> bool {
+  c == '#' || c == 'o'
+}
+
+fn get_bounds(paths: &[Vec<Point>]) -> (Point, Point) {
+  let (mut min_x, mut max_x) = (1_000_000_000, 0);
+  let (mut min_y, mut max_y) = (1_000_000_000, 0);
+  for path in paths {
+    for point in path {
+      min_x = min(min_x, point.x);
+      max_x = max(max_x, point.x);
+      min_y = min(min_y, point.y);
+      max_y = max(max_y, point.y);
+    }
+  }
+  (Point { x: min_x, y: min_y }, Point { x: max_x, y: max_y })
+}
+
+fn get_range(a: usize, b: usize) -> (usize, usize) {
+  if a > b {
+    (b, a+1)
+  } else {
+    (a, b+1)
+  }
+}
+
+pub fn parse_input(lines: Lines<'_>) -> Result<Vec<Vec<Point>>, ParsePathError> {
+  lines.map(|line| {
+    match parse_path(line) {
+      Ok(("", points)) => Ok(points),
+      _ => Err(ParsePathError { message: format!("not a valid path: {}", line) })
+    }
+  }).collect()
+}
+
+fn parse_path(input: &str) -> IResult<&str, Vec<Point>> {
+  let (input, points) = many1(parse_point)(input)?;
+  Ok((input, points))
+}
+
+fn parse_point(input: &str) -> IResult<&str, Point> {
+  let (input, (x, _, y)) = tuple(
+    (parse_number, tag(","), parse_number)
+  )(input)?;
+  let (input, _) = opt(tag(" -> "))(input)?;
+
+  Ok((input, Point { x, y }))
+}
+
+// coordinates are limited to u32 so that they can be safely used in signed arithmetic.
+fn parse_number(i: &str) -> IResult<&str, usize> {
+  map_res(
+    take_while1(|c: char| c.is_ascii_digit()),
+    |s: &str| {
+      s.parse::<u32>().map(|n| n as usize)
+    })(i)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePathError {
+  pub message: String
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_parse_path() {
+    let (_, path) = parse_path("457,106 -> 457,99 -> 457,106 -> 459,106").unwrap();
+    assert_eq!(path, vec![
+      Point { x: 457, y: 106 },
+      Point { x: 457, y: 99 },
+      Point { x: 457, y: 106 },
+      Point { x: 459, y: 106 },
+    ]);
+  }
+
+  #[test]
+  pub fn test_sand_units() {
+    let paths = parse_input(include_str!("../../inputs/day14.sample.txt").lines()).unwrap();
+    assert_eq!(24, min_units_for_infinite_flow(&paths, SPIGOT.x).unwrap().0);
+    assert_eq!(93, min_units_until_full(&paths, SPIGOT.x).unwrap().0);
+    // sand poured in past the rocks falls straight into the abyss, or piles up on the floor
+    assert_eq!(0, min_units_for_infinite_flow(&paths, 510).unwrap().0);
+    assert_eq!(116, min_units_until_full(&paths, 510).unwrap().0);
+  }
+
+  #[test]
+  pub fn test_malformed_paths() {
+    assert!(parse_input("1,2 -> x".lines()).is_err());
+    assert!(parse_input("1,2 -> 3".lines()).is_err());
+    assert!(parse_input("99999999999,2".lines()).is_err());
+    // rocks far away from the spigot, or at the very edge of the scan
+    let paths = parse_input("0,3 -> 4000000000,3\n0,0 -> 0,1".lines()).unwrap();
+    assert_eq!(9, min_units_for_infinite_flow(&paths, SPIGOT.x).unwrap().0);
+    assert_eq!(9, min_units_until_full(&paths, SPIGOT.x).unwrap().0);
+    // a spigot inside a rock has nowhere to pour
+    assert_eq!(0, min_units_until_full(&paths, 0).unwrap().0);
+    let paths = parse_input("500,5000 -> 501,5000".lines()).unwrap();
+    assert!(min_units_for_infinite_flow(&paths, SPIGOT.x).is_err());
+  }
+}
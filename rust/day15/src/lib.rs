@@ -0,0 +1,290 @@
+use std::str::Lines;
+use std::cmp::{min, max};
+
+use nom::IResult;
+use nom::bytes::complete::{tag, take_while1};
+use nom::combinator::{map_res};
+use nom::sequence::{tuple};
+
+// The row part 1 counts impossible positions in, and how far part 2 looks for the open
+// position, for the real input.
+pub const ROW: i64 = 2_000_000;
+pub const MAX_SIDE: i64 = 4_000_000;
+
+pub fn impossible_positions(pairs: &[(Point, Point)], pos: i64) -> i64 {
+  // Improve on the brute force version, by just looking for ranges of
+  // points that must be filled in. For a given sensor, beacon, and position line,
+  // we can calculate the range by subtracting the distance from S to P from S's
+  // manhattan distance to B, so we end up with a range of [s.x-leftover, s.x+leftover]
+  //
+  //    .....S.....
+  //    .....#.....
+  // P  .#########.
+  //    ...........
+  //    .........B.
+  // Then we just have to merge the ranges, and add up the range lengths to figure
+  // how many positions another beacon cannot be in.
+  let mut ranges: Vec<Range> = vec![];
+  for (sensor, beacon) in pairs {
+    let distance = manhattan_distance(sensor, beacon);
+    let line_distance = pos - sensor.y;
+    if line_distance.abs() > distance {
+      continue;
+    }
+
+    ranges.push(Range {
+      start: sensor.x - (distance - line_distance.abs()),
+      stop: sensor.x + (distance - line_distance.abs()),
+    });
+  }
+
+  let ranges = merge_ranges(&ranges);
+
+  // dbg!(&keep_ranges);
+
+  ranges
+    .iter()
+    .map(|r| r.stop - r.start)
+    .sum()
+}
+
+pub fn find_open_position(pairs: &[(Point, Point)], max_side: i64) -> Option<Point> {
+  let x_range = Range { start: 0, stop: max_side };
+  for y in 0..max_side {
+    if let Some(x) = open_position(pairs, y, &x_range) {
+      return Some(Point { x, y });
+    }
+  }
+  None
+}
+
+fn open_position(pairs: &[(Point, Point)], pos: i64, x_range: &Range) -> Option<i64> {
+  let mut ranges: Vec<Range> = vec![];
+  for (sensor, beacon) in pairs {
+    let distance = manhattan_distance(sensor, beacon);
+    let line_distance = pos - sensor.y;
+    if line_distance.abs() > distance {
+      continue;
+    }
+
+    ranges.push(Range {
+      start: sensor.x - (distance - line_distance.abs()),
+      stop: sensor.x + (distance - line_distance.abs()),
+    });
+  }
+
+  let ranges = merge_ranges(&ranges);
+
+  // dbg!(&ranges);
+  // the first position in x_range that isn't covered by a sensor.
+  let mut x = x_range.start;
+  for range in ranges {
+    if range.start > x {
+      break;
+    }
+    x = max(x, range.stop + 1);
+  }
+  if x <= x_range.stop { Some(x) } else { None }
+}
+
+fn merge_ranges(ranges: &[Range]) -> Vec<Range> {
+  let mut ranges = ranges.to_vec();
+  let mut keep_ranges = vec![];
+  ranges.sort_by_key(|r| r.start);
+  // dbg!(&ranges);
+
+  let mut indices = 0..ranges.len();
+  let Some(last) = indices.next() else {
+    return keep_ranges;
+  };
+  keep_ranges.push(ranges[last]);
+  for current in indices {
+    // println!("looking at range {}", current);
+    let last_range = keep_ranges.last_mut().unwrap();
+    if last_range.overlaps(&ranges[current]) {
+      // println!("{:?} overlaps {:?}", ranges[last], ranges[current]);
+      last_range.stop = max(last_range.stop, ranges[current].stop);
+    } else {
+      // println!("{:?} DOES NOT overlap {:?}", ranges[last], ranges[current]);
+      keep_ranges.push(ranges[current]);
+    }
+  }
+
+  keep_ranges
+}
+
+fn get_bounds(pairs: &[(Point, Point)]) -> (Point, Point) {
+  let mut min_corner = Point { x: 1_000_000_000, y: 1_000_000_000 };
+  let mut max_corner = Point { x: 0, y: 0};
+  for (sensor, beacon) in pairs {
+    min_corner.y = min(min_corner.y, min(sensor.y - manhattan_distance(sensor, beacon), beacon.y));
+    min_corner.x = min(min_corner.x, min(sensor.x - manhattan_distance(sensor, beacon), beacon.x));
+    max_corner.y = max(max_corner.y, max(sensor.y + manhattan_distance(sensor, beacon), beacon.y));
+    max_corner.x = max(max_corner.x, max(sensor.x + manhattan_distance(sensor, beacon), beacon.x));
+  }
+  (min_corner, max_corner)
+}
+
+pub fn impossible_positions_brute(pairs: &[(Point, Point)], pos: i64) -> i64 {
+  let (min_corner, max_corner) = get_bounds(pairs);
+  println!("found corners: {:?} {:?}", min_corner, max_corner);
+
+  // account for possible negative coordinates in our min_corner by
+  // possibly translating them for screen coordinates
+  let (tran_x, tran_y) = (
+    if min_corner.x < 0 { min_corner.x.abs() } else { 0 },
+    if min_corner.y < 0 { min_corner.y.abs() } else { 0 },
+  );
+  let mut screen = vec![vec!['.'; (max_corner.x - min_corner.x).unsigned_abs() as usize + 1]; (max_corner.y - min_corner.y).unsigned_abs() as usize + 1];
+  println!("built screen");
+
+  for (sensor, beacon) in pairs {
+    // println!("filling in S={:?} B={:?}", sensor, beacon);
+    screen[(sensor.y + tran_y) as usize][(sensor.x + tran_x) as usize] = 'S';
+    screen[(beacon.y + tran_y) as usize][(beacon.x + tran_x) as usize] = 'B';
+    // fill in areas other beacons cannot be.
+    let distance = manhattan_distance(sensor, beacon);
+    for y in (sensor.y-distance)..(sensor.y+distance+1) {
+      for x in (sensor.x-distance)..(sensor.x+distance+1) {
+        // if sensor.x != 8 || sensor.y != 7 {
+        //   continue;
+        // }
+        if manhattan_distance(&Point { x, y }, sensor) > distance {
+          continue;
+        }
+        // if x < min_corner.x || x >= max_corner.x || y < min_corner.y || y >= max_corner.y {
+        //   continue;
+        // }
+        let (y, x) = ((y + tran_y) as usize, (x + tran_x) as usize);
+        if screen[y][x] == '.' {
+          screen[y][x] = '#';
+        }
+      }
+    }
+  }
+
+  print_screen(&min_corner, &screen);
+
+  let line = (pos + tran_y) as usize;
+  // println!("{}", String::from_iter(screen[line].to_vec()));
+  screen[line]
+    .iter()
+    // .filter(|c| **c == '#' || **c == 'B' || **c == 'S')
+    .filter(|c| **c == '#')
+    .count() as i64
+}
+
+fn manhattan_distance(p1: &Point, p2: &Point) -> i64 {
+  (p1.x - p2.x).abs() + (p1.y - p2.y).abs()
+}
+
+fn print_screen(min: &Point, screen: &[Vec<char>]) {
+  // let mut line = 0;
+  // for x in (min.x)..(min.x+(screen[0].len() as i64) +1) {
+  //   if x % 5 == 0 && x < 0 {
+  //     buf[x] = '-';
+  //   }
+  // }
+  // let buf = String::from_iter(vec![' '; screen[0].len()]);
+  // for x in (min.x)..(min.x+(screen[0].len() as i64) +1) {
+  //   if x % 5 == 0 && x < 0 {
+  //     buf[x] = '-';
+  //   }
+  // }
+  for (line, row) in (min.y..).zip(screen) {
+    println!("{:4} {}", line, String::from_iter(row));
+  }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Point {
+  x: i64,
+  y: i64,
+}
+
+impl Point {
+  pub fn tuning_frequency(&self) -> i64 {
+    self.x * 4_000_000 + self.y
+  }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+struct Range {
+  start: i64,
+  stop: i64,
+}
+
+impl Range {
+  fn overlaps(&self, other: &Range) -> bool {
+    self.start <= other.start && other.start <= self.stop
+  }
+}
+
+pub fn parse_lines(lines: Lines<'_>) -> Result<Vec<(Point, Point)>, ParseSensorError> {
+  lines.map(|line| {
+    match parse_line(line) {
+      Ok(("", pair)) => Ok(pair),
+      _ => Err(ParseSensorError { message: format!("not a valid sensor report: {}", line) })
+    }
+  })
+  .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSensorError {
+  pub message: String
+}
+
+// Sensor at x=9, y=16: closest beacon is at x=10, y=16
+fn parse_line(input: &str) -> IResult<&str, (Point, Point)> {
+  let (input, _) = tag("Sensor at ")(input)?;
+  let (input, sensor) = parse_point(input)?;
+  let (input, _) = tag(": closest beacon is at ")(input)?;
+  let (input, beacon) = parse_point(input)?;
+  Ok(
+    (input, (sensor, beacon))
+  )
+}
+
+fn parse_point(input: &str) -> IResult<&str, Point> {
+  let (input, (_, x, _, y)) = tuple(
+    (tag("x="), parse_number, tag(", y="), parse_number)
+  )(input)?;
+  Ok((input, Point { x, y }))
+}
+
+// coordinates are limited to i32 so that distances between them can't overflow an i64.
+fn parse_number(i: &str) -> IResult<&str, i64> {
+  map_res(
+    take_while1(|c: char| c.is_ascii_digit() || c == '-'),
+    |s: &str| {
+      s.parse::<i32>().map(|n| n as i64)
+    })(i)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_sample() {
+    let pairs = parse_lines(include_str!("../../inputs/day15.sample.txt").lines()).unwrap();
+    assert_eq!(26, impossible_positions(&pairs, 10));
+    assert_eq!(Some(Point { x: 14, y: 11 }), find_open_position(&pairs, 20));
+    assert_eq!(56000011, find_open_position(&pairs, 20).unwrap().tuning_frequency());
+  }
+
+  #[test]
+  pub fn test_malformed_reports() {
+    assert!(parse_lines("Sensor at x=1, y=2".lines()).is_err());
+    assert!(parse_lines("Sensor at x=1, y=2: closest beacon is at x=3, y=4 and more".lines()).is_err());
+    assert!(parse_lines("Sensor at x=9223372036854775807, y=0: closest beacon is at x=-9223372036854775808, y=0".lines()).is_err());
+    // no sensors at all, or sensors that leave gaps on both sides of the row
+    assert_eq!(0, impossible_positions(&[], 10));
+    assert_eq!(Some(Point { x: 0, y: 0 }), find_open_position(&[], 20));
+    let pairs = parse_lines("Sensor at x=5, y=0: closest beacon is at x=6, y=0\nSensor at x=10, y=0: closest beacon is at x=11, y=0".lines()).unwrap();
+    assert_eq!(Some(Point { x: 0, y: 0 }), find_open_position(&pairs, 20));
+  }
+}
+
+
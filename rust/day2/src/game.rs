@@ -0,0 +1,246 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::Lines;
+
+use crate::{Match, ParseMatchError, Shape};
+
+// Rock paper scissors with any odd number of shapes, arranged in a circle where each shape
+// beats the (n-1)/2 shapes before it and loses to the (n-1)/2 after it. With 3 shapes this
+// is the puzzle's game, and ordinals.rs's modular arithmetic generalised.
+//
+// The strategy guide's second column is either your shape, or the outcome to aim for as an
+// offset around the circle from their shape, in the same order: for rock paper scissors X,
+// Y and Z are -1 (the shape that loses), 0 (the same shape) and +1 (the shape that wins).
+// Larger games can ask for a bigger win or loss.
+#[derive(Debug, Clone)]
+pub struct Game {
+  shapes: Vec<String>,
+  shape_scores: Vec<i32>,
+  // points for losing, drawing and winning a round.
+  outcome_scores: [i32; 3],
+  their_symbols: HashMap<String, usize>,
+  your_symbols: HashMap<String, usize>,
+}
+
+// A shape, in the game it belongs to.
+#[derive(Clone, Copy)]
+pub struct CyclicMove<'a> {
+  game: &'a Game,
+  index: usize,
+}
+
+impl Game {
+  // Shapes score 1, 2, 3, ... in order, and rounds 0, 3 and 6 like the puzzle. There must
+  // be a symbol for each shape for both players.
+  pub fn new(shapes: &[&str], their_symbols: &[&str], your_symbols: &[&str]) -> Result<Game, GameError> {
+    let n = shapes.len();
+    if n.is_multiple_of(2) {
+      return Err(GameError { message: format!("needs an odd number of shapes, not {}", n) });
+    }
+    symbol_table("shapes", shapes, n)?;
+    Ok(Game {
+      shapes: shapes.iter().map(|s| s.to_string()).collect(),
+      shape_scores: (1..=n as i32).collect(),
+      outcome_scores: [0, 3, 6],
+      their_symbols: symbol_table("their moves", their_symbols, n)?,
+      your_symbols: symbol_table("your moves", your_symbols, n)?,
+    })
+  }
+
+  pub fn with_scores(mut self, shape_scores: &[i32], outcome_scores: [i32; 3]) -> Result<Game, GameError> {
+    if shape_scores.len() != self.shapes.len() {
+      return Err(GameError {
+        message: format!("needs a score for each of the {} shapes, not {}", self.shapes.len(), shape_scores.len())
+      });
+    }
+    self.shape_scores = shape_scores.to_vec();
+    self.outcome_scores = outcome_scores;
+    Ok(self)
+  }
+
+  pub fn rock_paper_scissors() -> Game {
+    Game::new(&["rock", "paper", "scissors"], &["A", "B", "C"], &["X", "Y", "Z"]).unwrap()
+  }
+
+  pub fn rock_paper_scissors_lizard_spock() -> Game {
+    Game::new(&["rock", "spock", "paper", "lizard", "scissors"], &["A", "B", "C", "D", "E"], &["V", "W", "X", "Y", "Z"])
+      .unwrap()
+  }
+
+  pub fn shapes(&self) -> Vec<CyclicMove<'_>> {
+    (0..self.shapes.len()).map(|index| CyclicMove { game: self, index }).collect()
+  }
+
+  pub fn shape(&self, name: &str) -> Option<CyclicMove<'_>> {
+    self.shapes.iter().position(|s| s == name).map(|index| CyclicMove { game: self, index })
+  }
+
+  pub fn parse_match(&self, s: &str) -> Result<Match<CyclicMove<'_>>, ParseMatchError> {
+    let mut moves = s.split(' ');
+    let theirs = moves.next().ok_or(ParseMatchError::ExpectedMove { s: s.to_string() })?;
+    let yours = moves.next().ok_or(ParseMatchError::ExpectedMove { s: s.to_string() })?;
+    let their_move = self.their_symbols.get(theirs).ok_or(ParseMatchError::InvalidMove { s: s.to_string() })?;
+    let your_move = self.your_symbols.get(yours).ok_or(ParseMatchError::InvalidMove { s: s.to_string() })?;
+    Ok(Match::new(
+      CyclicMove { game: self, index: *their_move },
+      CyclicMove { game: self, index: *your_move },
+      *your_move as i32 - (self.shapes.len() / 2) as i32,
+    ))
+  }
+
+  pub fn predict_score_from_move(&self, lines: Lines<'_>) -> Result<i32, ParseMatchError> {
+    Ok(
+      lines.map(|l| self.parse_match(l))
+        .collect::<Result<Vec<_>,_>>()?
+        .iter()
+        .fold(0, |acc, m1| acc + m1.score_from_move())
+    )
+  }
+
+  pub fn predict_score_from_result(&self, lines: Lines<'_>) -> Result<i32, ParseMatchError> {
+    Ok(
+      lines.map(|l| self.parse_match(l))
+        .collect::<Result<Vec<_>,_>>()?
+        .iter()
+        .fold(0, |acc, m1| acc + m1.score_from_result())
+    )
+  }
+}
+
+// Maps each symbol to its position, checking there are `n` of them and none repeat.
+fn symbol_table(what: &str, symbols: &[&str], n: usize) -> Result<HashMap<String, usize>, GameError> {
+  if symbols.len() != n {
+    return Err(GameError { message: format!("needs {} {}, not {}", n, what, symbols.len()) });
+  }
+  let mut table = HashMap::new();
+  for (i, symbol) in symbols.iter().enumerate() {
+    if symbol.is_empty() || symbol.contains(char::is_whitespace) {
+      return Err(GameError { message: format!("{} can't be blank or contain spaces: {:?}", what, symbol) });
+    }
+    if table.insert(symbol.to_string(), i).is_some() {
+      return Err(GameError { message: format!("{} has {} more than once", what, symbol) });
+    }
+  }
+  Ok(table)
+}
+
+impl CyclicMove<'_> {
+  pub fn name(&self) -> &str {
+    &self.game.shapes[self.index]
+  }
+}
+
+impl Shape for CyclicMove<'_> {
+  type Outcome = i32;
+
+  fn score(&self) -> i32 {
+    self.game.shape_scores[self.index]
+  }
+
+  fn get_move(&self, offset: i32) -> Self {
+    let n = self.game.shapes.len() as i64;
+    CyclicMove { game: self.game, index: (self.index as i64 + offset as i64).rem_euclid(n) as usize }
+  }
+
+  fn outcome_score(&self, ord: Ordering) -> i32 {
+    self.game.outcome_scores[(ord as i8 + 1) as usize]
+  }
+}
+
+impl Ord for CyclicMove<'_> {
+  // the shapes up to half way back around the circle are the ones this shape beats.
+  fn cmp(&self, other: &Self) -> Ordering {
+    let n = self.game.shapes.len();
+    match (self.index + n - other.index) % n {
+      0 => Ordering::Equal,
+      distance if distance <= n / 2 => Ordering::Greater,
+      _ => Ordering::Less,
+    }
+  }
+}
+
+impl PartialOrd for CyclicMove<'_> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl PartialEq for CyclicMove<'_> {
+  fn eq(&self, other: &Self) -> bool {
+    self.index == other.index
+  }
+}
+
+impl Eq for CyclicMove<'_> {}
+
+impl fmt::Debug for CyclicMove<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameError {
+  pub message: String
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_same_as_rock_paper_scissors() {
+    let game = Game::rock_paper_scissors();
+    let input = include_str!("../../inputs/day2.txt");
+    assert_eq!(crate::predict_score_from_move(input.lines()).unwrap(), game.predict_score_from_move(input.lines()).unwrap());
+    assert_eq!(crate::predict_score_from_result(input.lines()).unwrap(), game.predict_score_from_result(input.lines()).unwrap());
+    assert_eq!(Ok(15), game.predict_score_from_move("A Y\nB X\nC Z".lines()));
+    assert_eq!(Ok(12), game.predict_score_from_result("A Y\nB X\nC Z".lines()));
+  }
+
+  #[test]
+  pub fn test_lizard_spock() {
+    let game = Game::rock_paper_scissors_lizard_spock();
+    let shape = |name| game.shape(name).unwrap();
+    for (winner, loser) in [
+      ("scissors", "paper"), ("paper", "rock"), ("rock", "lizard"), ("lizard", "spock"), ("spock", "scissors"),
+      ("scissors", "lizard"), ("lizard", "paper"), ("paper", "spock"), ("spock", "rock"), ("rock", "scissors"),
+    ] {
+      assert_eq!(Ordering::Greater, shape(winner).cmp(&shape(loser)), "{} beats {}", winner, loser);
+      assert_eq!(Ordering::Less, shape(loser).cmp(&shape(winner)), "{} loses to {}", loser, winner);
+    }
+    for s in game.shapes() {
+      assert_eq!(2, game.shapes().iter().filter(|other| s > **other).count());
+    }
+
+    // rock vs paper (lose, 0 + 1), spock vs spock (draw, 3 + 2), scissors vs lizard (win, 6 + 5)
+    assert_eq!(Ok(17), game.predict_score_from_move("C V\nB W\nD Z".lines()));
+    // to lose big against paper, play rock; to win big against rock, play paper
+    assert_eq!(Ok(1 + 9), game.predict_score_from_result("C V\nA Z".lines()));
+  }
+
+  #[test]
+  pub fn test_larger_games() {
+    let shapes = ["a", "b", "c", "d", "e", "f", "g"];
+    let game = Game::new(&shapes, &shapes, &shapes).unwrap().with_scores(&[0; 7], [-1, 0, 1]).unwrap();
+    // g beats the three before it, and loses to the three after it
+    assert_eq!(Ok(1 + 1 + 1 - 1 - 1), game.predict_score_from_move("d g\ne g\nf g\na g\nc g".lines()));
+    // a is the biggest loss (-1), d a draw (0), and g the biggest win (+1)
+    assert_eq!(Ok(0), game.predict_score_from_result("c a\nc d\nc g".lines()));
+  }
+
+  #[test]
+  pub fn test_invalid_games() {
+    assert!(Game::new(&["a", "b"], &["A", "B"], &["X", "Y"]).is_err());
+    assert!(Game::new(&[], &[], &[]).is_err());
+    assert!(Game::new(&["a", "a", "c"], &["A", "B", "C"], &["X", "Y", "Z"]).is_err());
+    assert!(Game::new(&["a", "b", "c"], &["A", "B"], &["X", "Y", "Z"]).is_err());
+    assert!(Game::new(&["a", "b", "c"], &["A", "A", "B"], &["X", "Y", "Z"]).is_err());
+    assert!(Game::new(&["a", "b", "c"], &["A", "B", "C"], &["X", "Y", "Z Z"]).is_err());
+    assert!(Game::new(&["a", "b", "c"], &["A", "B", "C"], &["X", "Y", ""]).is_err());
+    assert!(Game::rock_paper_scissors().with_scores(&[1, 2], [0, 3, 6]).is_err());
+    assert!(Game::rock_paper_scissors().predict_score_from_move("A".lines()).is_err());
+    assert!(Game::rock_paper_scissors().predict_score_from_move("D X".lines()).is_err());
+  }
+}
@@ -0,0 +1,361 @@
+use std::cmp::{Ord, Ordering};
+use std::fmt;
+use std::str::FromStr;
+pub mod game;
+pub mod optimiser;
+mod ordinals;
+pub mod rules;
+pub mod tournament;
+
+use std::str::Lines;
+
+// NOTE: I was trying to figure out how to instead accept an Iterator of string instead, since it felt
+// more flexible and less tied to strings. (for instance it'd pipe better into file i/o if we didn't
+// read everything into a string). It is possible, but more verbose, and involves declaring lifetimes
+// pub fn predict_score_from_move<'a>(lines: impl Iterator<Item = &'a str>) -> Result<i32, ParseMatchError> {
+pub fn predict_score_from_move(lines: Lines<'_>) -> Result<i32, ParseMatchError> {
+  predict_score_from_move_by(lines, Method::DEFAULT)
+}
+
+pub fn predict_score_from_result(lines: Lines<'_>) -> Result<i32, ParseMatchError> {
+  predict_score_from_result_by(lines, Method::DEFAULT)
+}
+
+pub fn predict_score_from_move_by(lines: Lines<'_>, method: Method) -> Result<i32, ParseMatchError> {
+  Ok(
+    lines.map(Match::from_str)
+      .collect::<Result<Vec<Match>,_>>()?
+      .iter()
+      .fold(0, |acc, m1| acc + m1.score_from_move_by(method))
+  )
+}
+
+pub fn predict_score_from_result_by(lines: Lines<'_>, method: Method) -> Result<i32, ParseMatchError> {
+  Ok(
+    lines.map(Match::from_str)
+      .collect::<Result<Vec<Match>,_>>()?
+      .iter()
+      .fold(0, |acc, m1| acc + m1.score_from_result_by(method))
+  )
+}
+
+// A shape in a game like rock paper scissors, where every shape beats some of the others
+// (Greater), loses to the rest (Less), and scores points just for being played.
+pub trait Shape: Ord + Copy {
+  // What a strategy guide can ask for instead of a shape. For rock paper scissors, whether
+  // to lose, draw or win.
+  type Outcome: Copy;
+
+  fn score(&self) -> i32;
+
+  // The shape to play against this one to get `outcome`.
+  fn get_move(&self, outcome: Self::Outcome) -> Self;
+
+  // Points for a round that ended `ord` for whoever played this shape.
+  fn outcome_score(&self, ord: Ordering) -> i32 {
+    match ord {
+      Ordering::Greater => 6,
+      Ordering::Equal => 3,
+      Ordering::Less => 0,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Move {
+  Rocks,
+  Paper,
+  Scissors
+}
+
+// How Move works out who wins and which move gets a result: by looking it up in a table of
+// every case, or with modular arithmetic on the moves' positions (see ordinals.rs). They
+// always agree; the table is the default unless built with the `ordinals` feature.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Method {
+  Table,
+  Ordinal,
+}
+
+impl Method {
+  pub const DEFAULT: Method = if cfg!(feature = "ordinals") { Method::Ordinal } else { Method::Table };
+}
+
+impl FromStr for Method {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "table" => Ok(Method::Table),
+      "ordinal" => Ok(Method::Ordinal),
+      _ => Err(format!("unknown method {}, expected table or ordinal", s)),
+    }
+  }
+}
+
+impl Move {
+  pub fn score(&self) -> i32 {
+    match *self {
+      Self::Rocks => 1,
+      Self::Paper => 2,
+      Self::Scissors => 3,
+    }
+  }
+
+  pub fn get_move(&self, ord: Ordering) -> Move {
+    self.get_move_by(ord, Method::DEFAULT)
+  }
+
+  pub fn get_move_by(&self, ord: Ordering, method: Method) -> Move {
+    if method == Method::Ordinal {
+      return ordinals::get_move(*self, ord);
+    }
+    match (self, ord) {
+      (_, Ordering::Equal) => *self,
+      (Self::Rocks, Ordering::Less) => Self::Scissors,
+      (Self::Rocks, Ordering::Greater) => Self::Paper,
+      (Self::Paper, Ordering::Less) => Self::Rocks,
+      (Self::Paper, Ordering::Greater) => Self::Scissors,
+      (Self::Scissors, Ordering::Less) => Self::Paper,
+      (Self::Scissors, Ordering::Greater) => Self::Rocks,
+    }
+  }
+
+  pub fn cmp_by(&self, other: &Self, method: Method) -> Ordering {
+    if method == Method::Ordinal {
+      return ordinals::cmp(*self, *other);
+    }
+    match (self, other) {
+      (Self::Rocks, Self::Scissors) | (Self::Paper, Self::Rocks) | (Self::Scissors, Self::Paper) => Ordering::Greater,
+      (Self::Rocks, Self::Paper) | (Self::Paper, Self::Scissors) | (Self::Scissors, Self::Rocks) => Ordering::Less,
+      _ => Ordering::Equal,
+    }
+  }
+}
+
+impl Shape for Move {
+  type Outcome = Ordering;
+
+  fn score(&self) -> i32 {
+    Move::score(self)
+  }
+
+  fn get_move(&self, ord: Ordering) -> Move {
+    Move::get_move(self, ord)
+  }
+}
+
+impl Ord for Move {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.cmp_by(other, Method::DEFAULT)
+  }
+}
+
+impl PartialOrd for Move {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParseMoveError;
+
+impl FromStr for Move {
+  type Err = ParseMoveError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let m = match s {
+      "A" | "X" => Move::Rocks,
+      "B" | "Y" => Move::Paper,
+      "C" | "Z" => Move::Scissors,
+      _ => return Err(ParseMoveError{}),
+    };
+    Ok(m)
+  }
+}
+
+// A line of the strategy guide. The second column can be read as either the shape to play,
+// or the outcome to aim for.
+pub struct Match<M: Shape = Move> {
+  their_move: M,
+  your_move: M,
+  you_should_be: M::Outcome,
+}
+
+impl<M: Shape> Match<M> {
+  pub fn new(their_move: M, your_move: M, you_should_be: M::Outcome) -> Self {
+    Match { their_move, your_move, you_should_be }
+  }
+
+  pub fn score_from_move(&self) -> i32 {
+    score_round(self.your_move, self.their_move)
+  }
+
+  pub fn score_from_result(&self) -> i32 {
+    score_round(Shape::get_move(&self.their_move, self.you_should_be), self.their_move)
+  }
+}
+
+impl Match<Move> {
+  pub fn score_from_move_by(&self, method: Method) -> i32 {
+    let ord = self.your_move.cmp_by(&self.their_move, method);
+    self.your_move.outcome_score(ord) + self.your_move.score()
+  }
+
+  pub fn score_from_result_by(&self, method: Method) -> i32 {
+    let your_move = self.their_move.get_move_by(self.you_should_be, method);
+    your_move.outcome_score(your_move.cmp_by(&self.their_move, method)) + your_move.score()
+  }
+}
+
+// Your points for playing `your_move` against `their_move`.
+pub fn score_round<M: Shape>(your_move: M, their_move: M) -> i32 {
+  your_move.outcome_score(your_move.cmp(&their_move)) + your_move.score()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseMatchError {
+  ExpectedMove{ s: String },
+  InvalidMove{ s: String },
+  InvalidOrdering{ s: String }
+}
+
+impl fmt::Display for ParseMatchError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ParseMatchError::ExpectedMove { s } => write!(f, "expected two moves in: {}", s),
+      ParseMatchError::InvalidMove { s } => write!(f, "not a valid move in: {}", s),
+      ParseMatchError::InvalidOrdering { s } => write!(f, "not a valid outcome in: {}", s),
+    }
+  }
+}
+
+impl FromStr for Match {
+    type Err = ParseMatchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut moves = s.split(" ");
+        let their_move =
+          moves.next()
+               .ok_or(ParseMatchError::ExpectedMove { s: s.to_string() })?
+               .parse::<Move>().map_err(|_| ParseMatchError::InvalidMove { s: s.to_string() })?;
+        let next =
+          moves.next()
+               .ok_or(ParseMatchError::ExpectedMove { s: s.to_string() })?;
+        Ok(Match {
+          their_move,
+          your_move: next.parse().map_err(|_| ParseMatchError::InvalidMove { s: s.to_string() })?,
+          you_should_be: match next {
+            "X" => Ordering::Less,
+            "Y" => Ordering::Equal,
+            "Z" => Ordering::Greater,
+            _ => return Err(ParseMatchError::InvalidOrdering { s: s.to_string() })
+          }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::Move::*;
+  use std::cmp::Ordering::*;
+
+  #[test]
+  fn move_comparisons() {
+      assert_eq!(Rocks.cmp(&Scissors), Greater);
+      assert_eq!(Rocks.cmp(&Paper), Less);
+      assert_eq!(Paper.cmp(&Rocks), Greater);
+      assert_eq!(Paper.cmp(&Scissors), Less);
+      assert_eq!(Scissors.cmp(&Paper), Greater);
+      assert_eq!(Scissors.cmp(&Rocks), Less);
+      assert_eq!(Paper.cmp(&Paper), Equal);
+      assert_eq!(Scissors.cmp(&Scissors), Equal);
+  }
+
+  #[test]
+  fn test_match_score() {
+    assert_eq!(Match {
+        their_move: Rocks,
+        your_move: Paper,
+        you_should_be: Less,
+      }.score_from_move(), 8);
+    assert_eq!(Match {
+        their_move: Paper,
+        your_move: Rocks,
+        you_should_be: Greater,
+      }.score_from_move(), 1);
+    assert_eq!(Match {
+        their_move: Scissors,
+        your_move: Rocks,
+        you_should_be: Greater,
+      }.score_from_move(), 7);
+    assert_eq!(Match {
+        their_move: Paper,
+        your_move: Paper,
+        you_should_be: Greater,
+      }.score_from_move(), 5);
+  }
+
+  #[test]
+  fn test_move_get_move() {
+    assert_eq!(Move::Scissors, Move::Rocks.get_move(Less));
+    assert_eq!(Move::Paper, Move::Rocks.get_move(Greater));
+    assert_eq!(Move::Rocks, Move::Paper.get_move(Less));
+    assert_eq!(Move::Scissors, Move::Paper.get_move(Greater));
+    assert_eq!(Move::Paper, Move::Scissors.get_move(Less));
+    assert_eq!(Move::Rocks, Move::Scissors.get_move(Greater));
+  }
+
+  #[test]
+  fn test_match_parsing() {
+    assert_eq!(8, Match::from_str("A Y").unwrap().score_from_move());
+    assert_eq!(4, Match::from_str("A Y").unwrap().score_from_result());
+    assert_eq!(1, Match::from_str("B X").unwrap().score_from_move());
+    assert_eq!(1, Match::from_str("B X").unwrap().score_from_result());
+    assert_eq!(6, Match::from_str("C Z").unwrap().score_from_move());
+    assert_eq!(7, Match::from_str("C Z").unwrap().score_from_result());
+  }
+
+  #[test]
+  fn test_predict_score_from_move() {
+    assert_eq!(15, predict_score_from_move("A Y
+B X
+C Z".lines()).expect("oops"));
+  }
+
+  #[test]
+  fn test_predict_score_from_result() {
+    assert_eq!(12, predict_score_from_result("A Y
+B X
+C Z".lines()).expect("oops"));
+  }
+
+  #[test]
+  fn test_methods_agree() {
+    for m in [Rocks, Paper, Scissors] {
+      for other in [Rocks, Paper, Scissors] {
+        assert_eq!(m.cmp_by(&other, Method::Table), m.cmp_by(&other, Method::Ordinal), "{:?} vs {:?}", m, other);
+      }
+      for ord in [Less, Equal, Greater] {
+        assert_eq!(m.get_move_by(ord, Method::Table), m.get_move_by(ord, Method::Ordinal), "{:?} {:?}", m, ord);
+        assert_eq!(ord, m.get_move_by(ord, Method::Ordinal).cmp_by(&m, Method::Ordinal));
+      }
+    }
+
+    let input = include_str!("../../inputs/day2.txt");
+    for predict in [predict_score_from_move_by, predict_score_from_result_by] {
+      assert_eq!(predict(input.lines(), Method::Table), predict(input.lines(), Method::Ordinal));
+    }
+    assert_eq!(Ok(Method::Ordinal), "ordinal".parse());
+    assert!("modular".parse::<Method>().is_err());
+  }
+
+  #[test]
+  fn test_invalid_matches() {
+    assert_eq!(Err(ParseMatchError::ExpectedMove { s: "A".to_string() }), predict_score_from_move("A".lines()));
+    assert_eq!(Err(ParseMatchError::InvalidMove { s: "D X".to_string() }), predict_score_from_move("D X".lines()));
+    assert_eq!(Err(ParseMatchError::InvalidMove { s: "A W".to_string() }), predict_score_from_result("A W".lines()));
+    assert_eq!(Err(ParseMatchError::InvalidOrdering { s: "A B".to_string() }), predict_score_from_move("A B".lines()));
+  }
+}
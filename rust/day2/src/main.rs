@@ -1,236 +1,79 @@
-use std::cmp::{Ord, Ordering};
-use std::str::FromStr;
-use utils::cli::Cli;
-mod ordinals;
-
-use std::str::Lines;
-
-fn main() {
-  let args = Cli::parse();
-  let content = std::fs::read_to_string(&args.path).expect("could not read file");
-  println!("predicted score if you play: {}", predict_score_from_move(content.lines()).expect("couldn't predict score"));
-  println!("predicted score if you have suggested match result: {}", predict_score_from_result(content.lines()).expect("couldn't predict score"));
-}
+use std::path::Path;
 
-// NOTE: I was trying to figure out how to instead accept an Iterator of string instead, since it felt
-// more flexible and less tied to strings. (for instance it'd pipe better into file i/o if we didn't
-// read everything into a string). It is possible, but more verbose, and involves declaring lifetimes
-// fn predict_score_from_move<'a>(lines: impl Iterator<Item = &'a str>) -> Result<i32, ParseMatchError> {
-fn predict_score_from_move(lines: Lines<'_>) -> Result<i32, ParseMatchError> {
-  Ok(
-    lines.map(|l| Match::from_str(l))
-      .collect::<Result<Vec<Match>,_>>()?
-      .iter()
-      .fold(0, |acc, m1| acc + m1.score_from_move())
-  )
-}
+use day2::game::Game;
+use day2::optimiser::analyse_guide;
+use day2::tournament::{format_standings, guide_players, round_robin, Constant, Cycle, FrequencyCounter, Mirror, Random, Strategy};
+use day2::{predict_score_from_move_by, predict_score_from_result_by, Method, Move};
+use utils::cli::Cli;
 
-fn predict_score_from_result(lines: Lines<'_>) -> Result<i32, ParseMatchError> {
-  Ok(
-    lines.map(|l| Match::from_str(l))
-      .collect::<Result<Vec<Match>,_>>()?
-      .iter()
-      .fold(0, |acc, m1| acc + m1.score_from_result())
-  )
-}
+const USAGE: &str = "usage: day2 <file> [rpsls | --rules <file>] [--method <table|ordinal>] [analyse] \
+[tournament [--rounds <n>]]";
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd)]
-enum Move {
-  Rocks,
-  Paper,
-  Scissors
+struct Options {
+  game: Option<Game>,
+  analyse: bool,
+  method: Method,
+  tournament: bool,
+  rounds: usize,
 }
 
-impl Move {
-  pub fn score(&self) -> i32 {
-    match *self {
-      Self::Rocks => 1,
-      Self::Paper => 2,
-      Self::Scissors => 3,
-    }
-  }
-
-  pub fn get_move(&self, ord: Ordering) -> Move {
-    if ord == Ordering::Equal {
-      return self.clone();
+fn parse_args(args: &[String]) -> Result<Options, String> {
+  let mut options = Options { game: None, analyse: false, method: Method::DEFAULT, tournament: false, rounds: 1000 };
+  let mut rest = args.iter();
+  while let Some(arg) = rest.next() {
+    let mut value = |what: &str| rest.next().ok_or(format!("{} needs {}", arg, what));
+    match arg.as_str() {
+      "rpsls" => options.game = Some(Game::rock_paper_scissors_lizard_spock()),
+      "--rules" => {
+        let path = value("a file")?;
+        options.game = Some(Game::load_rules(Path::new(path)).map_err(|e| format!("bad rules: {}", e.message))?);
+      }
+      "--method" => options.method = value("table or ordinal")?.parse()?,
+      "analyse" => options.analyse = true,
+      "tournament" => options.tournament = true,
+      "--rounds" => options.rounds = value("a number")?.parse().map_err(|_| "--rounds needs a number")?,
+      arg => return Err(format!("unknown argument {}", arg)),
     }
-    match self {
-      Self::Rocks if ord == Ordering::Less => Self::Scissors,
-      Self::Rocks if ord == Ordering::Greater => Self::Paper,
-      Self::Paper if ord == Ordering::Less => Self::Rocks,
-      Self::Paper if ord == Ordering::Greater => Self::Scissors,
-      Self::Scissors if ord == Ordering::Less => Self::Paper,
-      Self::Scissors if ord == Ordering::Greater => Self::Rocks,
-      _ => panic!("Should not get here")
-    }
-  }
-}
-
-impl Ord for Move {
-  fn cmp(&self, other: &Self) -> Ordering {
-    if self == other {
-      return Ordering::Equal;
-    }
-
-    match *other {
-      Self::Rocks if *self == Self::Scissors => Ordering::Less,
-      Self::Rocks if *self == Self::Paper => Ordering::Greater,
-      Self::Paper if *self == Self::Rocks => Ordering::Less,
-      Self::Paper if *self == Self::Scissors => Ordering::Greater,
-      Self::Scissors if *self == Self::Paper => Ordering::Less,
-      Self::Scissors if *self == Self::Rocks => Ordering::Greater,
-      _ => panic!("Should not be here")
-    }
-  }
-}
-
-#[derive(Debug, Clone)]
-struct ParseMoveError;
-
-impl FromStr for Move {
-  type Err = ParseMoveError;
-
-  fn from_str(s: &str) -> Result<Self, Self::Err> {
-    let m = match s {
-      "A" | "X" => Move::Rocks,
-      "B" | "Y" => Move::Paper,
-      "C" | "Z" => Move::Scissors,
-      _ => return Err(ParseMoveError{}),
-    };
-    Ok(m)
   }
+  Ok(options)
 }
 
-struct Match {
-  their_move: Move,
-  your_move: Move,
-  you_should_be: Ordering,
-}
-
-impl Match {
-  pub fn score_from_move(&self) -> i32 {
-    (match self.your_move.cmp(&self.their_move) {
-      Ordering::Greater => 6,
-      Ordering::Equal => 3,
-      Ordering::Less => 0,
-    }) + self.your_move.score()
+fn main() {
+  let args = Cli::parse();
+  let content = std::fs::read_to_string(&args.path).expect("could not read file");
+  let Options { game, analyse, method, tournament, rounds } = match parse_args(&args.args) {
+    Ok(options) => options,
+    Err(message) => return println!("{}\n{}", message, USAGE),
+  };
+
+  if tournament {
+    // the guide's columns play along with the bots.
+    let [theirs, yours] = guide_players(content.lines()).expect("couldn't read guide");
+    let mut strategies: Vec<Box<dyn Strategy>> = vec![
+      Box::new(Constant(Move::Rocks)),
+      Box::new(Constant(Move::Paper)),
+      Box::new(Constant(Move::Scissors)),
+      Box::new(Cycle::new(&[Move::Rocks, Move::Paper, Move::Scissors]).expect("the cycle has moves")),
+      Box::new(FrequencyCounter),
+      Box::new(Mirror),
+      Box::new(Random::new(1)),
+      Box::new(theirs),
+      Box::new(yours),
+    ];
+    print!("{}", format_standings(&round_robin(&mut strategies, rounds)));
+    return;
   }
 
-  pub fn score_from_result(&self) -> i32 {
-    let your_move = self.their_move.get_move(self.you_should_be);
-    (match your_move.cmp(&self.their_move) {
-      Ordering::Greater => 6,
-      Ordering::Equal => 3,
-      Ordering::Less => 0,
-    }) + your_move.score()
-  }
-}
-
-#[derive(Debug, Clone)]
-enum ParseMatchError {
-  ExpectedMove{ s: String },
-  InvalidMove{ s: String },
-  InvalidOrdering{ s: String }
-}
-
-impl FromStr for Match {
-    type Err = ParseMatchError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut moves = s.split(" ");
-        let their_move =
-          moves.next()
-               .ok_or(ParseMatchError::ExpectedMove { s: s.to_string() })?
-               .parse::<Move>().map_err(|_| ParseMatchError::InvalidMove { s: s.to_string() })?;
-        let next =
-          moves.next()
-               .ok_or(ParseMatchError::ExpectedMove { s: s.to_string() })?;
-        Ok(Match {
-          their_move,
-          your_move: next.parse().map_err(|_| ParseMatchError::InvalidMove { s: s.to_string() })?,
-          you_should_be: match next {
-            "X" => Ordering::Less,
-            "Y" => Ordering::Equal,
-            "Z" => Ordering::Greater,
-            _ => return Err(ParseMatchError::InvalidOrdering { s: s.to_string() })
-          }
-        })
+  match (game, analyse) {
+    (Some(game), true) => print!("{}", game.analyse_guide(content.lines()).expect("couldn't analyse guide")),
+    (Some(game), false) => {
+      println!("predicted score if you play: {}", game.predict_score_from_move(content.lines()).expect("couldn't predict score"));
+      println!("predicted score if you have suggested match result: {}", game.predict_score_from_result(content.lines()).expect("couldn't predict score"));
+    }
+    (None, true) => print!("{}", analyse_guide(content.lines()).expect("couldn't analyse guide")),
+    (None, false) => {
+      println!("predicted score if you play: {}", predict_score_from_move_by(content.lines(), method).expect("couldn't predict score"));
+      println!("predicted score if you have suggested match result: {}", predict_score_from_result_by(content.lines(), method).expect("couldn't predict score"));
     }
-}
-
-#[cfg(test)]
-mod tests {
-  use super::*;
-  use super::Move::*;
-  use std::cmp::Ordering::*;
-
-  #[test]
-  fn move_comparisons() {
-      assert_eq!(Rocks.cmp(&Scissors), Greater);
-      assert_eq!(Rocks.cmp(&Paper), Less);
-      assert_eq!(Paper.cmp(&Rocks), Greater);
-      assert_eq!(Paper.cmp(&Scissors), Less);
-      assert_eq!(Scissors.cmp(&Paper), Greater);
-      assert_eq!(Scissors.cmp(&Rocks), Less);
-      assert_eq!(Paper.cmp(&Paper), Equal);
-      assert_eq!(Scissors.cmp(&Scissors), Equal);
-  }
-
-  #[test]
-  fn test_match_score() {
-    assert_eq!(Match {
-        their_move: Rocks,
-        your_move: Paper,
-        you_should_be: Less,
-      }.score_from_move(), 8);
-    assert_eq!(Match {
-        their_move: Paper,
-        your_move: Rocks,
-        you_should_be: Greater,
-      }.score_from_move(), 1);
-    assert_eq!(Match {
-        their_move: Scissors,
-        your_move: Rocks,
-        you_should_be: Greater,
-      }.score_from_move(), 7);
-    assert_eq!(Match {
-        their_move: Paper,
-        your_move: Paper,
-        you_should_be: Greater,
-      }.score_from_move(), 5);
-  }
-
-  #[test]
-  fn test_move_get_move() {
-    assert_eq!(Move::Scissors, Move::Rocks.get_move(Less));
-    assert_eq!(Move::Paper, Move::Rocks.get_move(Greater));
-    assert_eq!(Move::Rocks, Move::Paper.get_move(Less));
-    assert_eq!(Move::Scissors, Move::Paper.get_move(Greater));
-    assert_eq!(Move::Paper, Move::Scissors.get_move(Less));
-    assert_eq!(Move::Rocks, Move::Scissors.get_move(Greater));
-  }
-
-  #[test]
-  fn test_match_parsing() {
-    assert_eq!(8, Match::from_str("A Y").unwrap().score_from_move());
-    assert_eq!(4, Match::from_str("A Y").unwrap().score_from_result());
-    assert_eq!(1, Match::from_str("B X").unwrap().score_from_move());
-    assert_eq!(1, Match::from_str("B X").unwrap().score_from_result());
-    assert_eq!(6, Match::from_str("C Z").unwrap().score_from_move());
-    assert_eq!(7, Match::from_str("C Z").unwrap().score_from_result());
-  }
-
-  #[test]
-  fn test_predict_score_from_move() {
-    assert_eq!(15, predict_score_from_move("A Y
-B X
-C Z".lines()).expect("oops"));
-  }
-
-  #[test]
-  fn test_predict_score_from_result() {
-    assert_eq!(12, predict_score_from_result("A Y
-B X
-C Z".lines()).expect("oops"));
   }
 }
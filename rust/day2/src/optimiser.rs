@@ -0,0 +1,127 @@
+use std::fmt;
+use std::str::{FromStr, Lines};
+
+use crate::game::{CyclicMove, Game};
+use crate::{score_round, Match, Move, ParseMatchError, Shape};
+
+// How the strategy guide compares with other ways of playing against the same opponent.
+//
+// Only their moves are taken from the guide. Everything is a total over all the rounds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis<M: Shape> {
+  pub rounds: usize,
+  // Playing the best shape against each of their moves, as if you knew it in advance.
+  pub best: i32,
+  // (shape, score) for always playing the same shape, in the order the shapes were given.
+  pub constant: Vec<(M, i32)>,
+  // (shape, number of rounds they played it).
+  pub their_moves: Vec<(M, usize)>,
+  // The constant strategy that scores the most against how often they play each shape,
+  // which is the best you can do without knowing the order of their moves. Ties go to the
+  // shape given first.
+  pub best_response: (M, i32),
+  pub from_move: i32,
+  pub from_result: i32,
+}
+
+// Analyses the matches against every shape in `shapes`, which should be all of the game's.
+pub fn analyse<M: Shape>(matches: &[Match<M>], shapes: &[M]) -> Analysis<M> {
+  let best_against = |their_move: M| shapes.iter().map(|s| score_round(*s, their_move)).max().unwrap_or(0);
+  let constant: Vec<(M, i32)> = shapes
+    .iter()
+    .map(|s| (*s, matches.iter().map(|m| score_round(*s, m.their_move)).sum()))
+    .collect();
+  let best_response = constant
+    .iter()
+    .copied()
+    .reduce(|best, next| if next.1 > best.1 { next } else { best })
+    .expect("needs at least one shape");
+
+  Analysis {
+    rounds: matches.len(),
+    best: matches.iter().map(|m| best_against(m.their_move)).sum(),
+    their_moves: shapes.iter().map(|s| (*s, matches.iter().filter(|m| m.their_move == *s).count())).collect(),
+    constant,
+    best_response,
+    from_move: matches.iter().map(|m| m.score_from_move()).sum(),
+    from_result: matches.iter().map(|m| m.score_from_result()).sum(),
+  }
+}
+
+// Analyses a rock paper scissors strategy guide.
+pub fn analyse_guide(lines: Lines<'_>) -> Result<Analysis<Move>, ParseMatchError> {
+  let matches = lines.map(Match::from_str).collect::<Result<Vec<Match>, _>>()?;
+  Ok(analyse(&matches, &[Move::Rocks, Move::Paper, Move::Scissors]))
+}
+
+impl Game {
+  pub fn analyse_guide(&self, lines: Lines<'_>) -> Result<Analysis<CyclicMove<'_>>, ParseMatchError> {
+    let matches = lines.map(|l| self.parse_match(l)).collect::<Result<Vec<_>, _>>()?;
+    Ok(analyse(&matches, &self.shapes()))
+  }
+}
+
+impl<M: Shape + fmt::Debug> fmt::Display for Analysis<M> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let short = |score: i32| format!("{} ({} short of best)", score, self.best - score);
+    writeln!(f, "rounds: {}", self.rounds)?;
+    writeln!(f, "best possible score: {}", self.best)?;
+    writeln!(f, "their moves:")?;
+    for (shape, count) in &self.their_moves {
+      let percent = if self.rounds == 0 { 0.0 } else { *count as f64 * 100.0 / self.rounds as f64 };
+      writeln!(f, "  {:?}: {} ({:.1}%)", shape, count, percent)?;
+    }
+    writeln!(f, "always playing the same shape:")?;
+    for (shape, score) in &self.constant {
+      writeln!(f, "  {:?}: {}", shape, short(*score))?;
+    }
+    writeln!(f, "best response to their moves: {:?}, {}", self.best_response.0, short(self.best_response.1))?;
+    writeln!(f, "guide as moves: {}", short(self.from_move))?;
+    writeln!(f, "guide as results: {}", short(self.from_result))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::Move::*;
+
+  #[test]
+  pub fn test_analyse_guide() {
+    let analysis = analyse_guide("A Y\nB X\nC Z".lines()).unwrap();
+    assert_eq!(3, analysis.rounds);
+    // paper, scissors then rock win every round: 8 + 9 + 7
+    assert_eq!(24, analysis.best);
+    assert_eq!(vec![(Rocks, 12), (Paper, 15), (Scissors, 18)], analysis.constant);
+    assert_eq!(vec![(Rocks, 1), (Paper, 1), (Scissors, 1)], analysis.their_moves);
+    assert_eq!((Scissors, 18), analysis.best_response);
+    assert_eq!((15, 12), (analysis.from_move, analysis.from_result));
+    assert!(analysis.to_string().ends_with("guide as moves: 15 (9 short of best)\nguide as results: 12 (12 short of best)\n"));
+
+    // against someone who mostly plays rock, paper is best even though it loses to scissors
+    let analysis = analyse_guide("A X\nA X\nA X\nC X".lines()).unwrap();
+    assert_eq!((Paper, 8 * 3 + 2), analysis.best_response);
+    assert_eq!(8 * 3 + 7, analysis.best);
+
+    let input = include_str!("../../inputs/day2.txt");
+    let analysis = analyse_guide(input.lines()).unwrap();
+    assert_eq!(Ok(analysis.from_move), crate::predict_score_from_move(input.lines()));
+    assert_eq!(Ok(analysis.from_result), crate::predict_score_from_result(input.lines()));
+    assert!(analysis.constant.iter().all(|(_, score)| *score <= analysis.best_response.1));
+    assert!(analysis.best_response.1 <= analysis.best && analysis.from_result <= analysis.best);
+  }
+
+  #[test]
+  pub fn test_analyse_game() {
+    let game = Game::rock_paper_scissors_lizard_spock();
+    let analysis = game.analyse_guide("A V\nA V".lines()).unwrap();
+    // spock and paper both beat rock, and paper scores more for being played
+    assert_eq!(2 * (6 + 3), analysis.best);
+    assert_eq!("paper", analysis.best_response.0.name());
+    assert_eq!(18, analysis.best_response.1);
+    // rock against rock draws
+    assert_eq!(2 * (3 + 1), analysis.from_move);
+    assert!(analysis.to_string().contains("\n  rock: 2 (100.0%)\n  spock: 0 (0.0%)\n"));
+    assert!(analyse_guide("A W".lines()).is_err());
+  }
+}
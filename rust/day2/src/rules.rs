@@ -0,0 +1,162 @@
+use std::path::Path;
+
+use crate::game::{Game, GameError};
+
+// Loads a game from a rules file, so that variants can be scored without changing the code:
+//
+//   shapes = ["rock", "paper", "scissors"]
+//   their_symbols = ["A", "B", "C"]
+//   your_symbols = ["X", "Y", "Z"]
+//   shape_scores = [1, 2, 3]
+//
+//   [outcome_scores]
+//   lose = 0
+//   draw = 3
+//   win = 6
+//
+// The shapes are listed so that each beats the ones up to half way back around the list
+// (see Game). The scores can be left out to use the puzzle's, but winning can't score less
+// than drawing, or drawing less than losing.
+impl Game {
+  pub fn load_rules(path: &Path) -> Result<Game, GameError> {
+    let content = std::fs::read_to_string(path).map_err(|e| GameError {
+      message: format!("could not read {}: {}", path.display(), e)
+    })?;
+    Game::from_rules(&content)
+  }
+
+  pub fn from_rules(content: &str) -> Result<Game, GameError> {
+    let table = content.parse::<toml::Table>().map_err(|e| GameError {
+      message: format!("not valid rules: {}", e)
+    })?;
+    for key in table.keys() {
+      if !["shapes", "their_symbols", "your_symbols", "shape_scores", "outcome_scores"].contains(&key.as_str()) {
+        return Err(GameError { message: format!("unknown rule {}", key) });
+      }
+    }
+
+    let shapes = strings(&table, "shapes")?;
+    let their_symbols = strings(&table, "their_symbols")?;
+    let your_symbols = strings(&table, "your_symbols")?;
+    let game = Game::new(&as_strs(&shapes), &as_strs(&their_symbols), &as_strs(&your_symbols))?;
+
+    let shape_scores = match table.get("shape_scores") {
+      Some(value) => {
+        let values = value.as_array().ok_or(GameError { message: "shape_scores should be a list".to_string() })?;
+        values.iter().map(|v| score("shape_scores", v)).collect::<Result<Vec<_>, _>>()?
+      }
+      None => (1..=shapes.len() as i32).collect(),
+    };
+    let outcome_scores = match table.get("outcome_scores") {
+      Some(toml::Value::Table(outcomes)) => {
+        for key in outcomes.keys() {
+          if !["lose", "draw", "win"].contains(&key.as_str()) {
+            return Err(GameError { message: format!("unknown outcome {}, expected lose, draw or win", key) });
+          }
+        }
+        let get = |key: &str| -> Result<i32, GameError> {
+          let value = outcomes.get(key).ok_or(GameError { message: format!("outcome_scores needs {}", key) })?;
+          score(&format!("outcome_scores.{}", key), value)
+        };
+        [get("lose")?, get("draw")?, get("win")?]
+      }
+      Some(_) => return Err(GameError { message: "outcome_scores should be a table".to_string() }),
+      None => [0, 3, 6],
+    };
+    if !(outcome_scores[0] <= outcome_scores[1] && outcome_scores[1] <= outcome_scores[2]) {
+      return Err(GameError {
+        message: format!("outcome scores should go up from lose to draw to win, not {:?}", outcome_scores)
+      });
+    }
+    game.with_scores(&shape_scores, outcome_scores)
+  }
+}
+
+fn strings(table: &toml::Table, key: &str) -> Result<Vec<String>, GameError> {
+  let error = || GameError { message: format!("{} should be a list of names", key) };
+  let values = table.get(key).ok_or(GameError { message: format!("rules need {}", key) })?;
+  values
+    .as_array()
+    .ok_or_else(error)?
+    .iter()
+    .map(|v| v.as_str().map(|s| s.to_string()).ok_or_else(error))
+    .collect()
+}
+
+fn as_strs(strings: &[String]) -> Vec<&str> {
+  strings.iter().map(|s| s.as_str()).collect()
+}
+
+fn score(name: &str, value: &toml::Value) -> Result<i32, GameError> {
+  value
+    .as_integer()
+    .and_then(|v| i32::try_from(v).ok())
+    .ok_or(GameError { message: format!("{} should be whole numbers, got {}", name, value) })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_rules_files() {
+    let rules = Game::from_rules(include_str!("../rules/rock_paper_scissors.toml")).unwrap();
+    let input = include_str!("../../inputs/day2.txt");
+    assert_eq!(crate::predict_score_from_move(input.lines()), rules.predict_score_from_move(input.lines()));
+    assert_eq!(crate::predict_score_from_result(input.lines()), rules.predict_score_from_result(input.lines()));
+
+    let rules = Game::from_rules(include_str!("../rules/rock_paper_scissors_lizard_spock.toml")).unwrap();
+    let game = Game::rock_paper_scissors_lizard_spock();
+    assert_eq!(game.predict_score_from_move("C V\nB W\nD Z".lines()), rules.predict_score_from_move("C V\nB W\nD Z".lines()));
+  }
+
+  #[test]
+  pub fn test_custom_rules() {
+    // scores are optional, and symbols can be longer than a letter
+    let rules = Game::from_rules(r#"
+shapes = ["rock", "paper", "scissors"]
+their_symbols = ["r", "p", "s"]
+your_symbols = ["rock", "paper", "scissors"]
+"#).unwrap();
+    assert_eq!(Ok(15), rules.predict_score_from_move("r paper\np rock\ns scissors".lines()));
+
+    // one point a win, nothing for the shape: a win, a loss and a draw
+    let rules = Game::from_rules(r#"
+shapes = ["rock", "paper", "scissors"]
+their_symbols = ["A", "B", "C"]
+your_symbols = ["X", "Y", "Z"]
+shape_scores = [0, 0, 0]
+outcome_scores = { lose = -1, draw = 0, win = 1 }
+"#).unwrap();
+    assert_eq!(Ok(0), rules.predict_score_from_move("A Y\nB X\nC Z".lines()));
+  }
+
+  #[test]
+  pub fn test_invalid_rules() {
+    let rules = |extra: &str| {
+      Game::from_rules(&format!("shapes = [\"a\", \"b\", \"c\"]\ntheir_symbols = [\"A\", \"B\", \"C\"]\n{}", extra))
+    };
+    assert!(rules("your_symbols = [\"X\", \"Y\", \"Z\"]").is_ok());
+    assert!(rules("").is_err());
+    assert!(rules("your_symbols = [\"X\", \"Y\"]").is_err());
+    assert!(rules("your_symbols = [\"X\", \"Y\", \"Y\"]").is_err());
+    assert!(rules("your_symbols = \"XYZ\"").is_err());
+    assert!(rules("your_symbols = [1, 2, 3]").is_err());
+    assert!(rules("your_symbols = [\"X\", \"Y\", \"Z\"]\nshape_scores = [1, 2]").is_err());
+    assert!(rules("your_symbols = [\"X\", \"Y\", \"Z\"]\nshape_scores = [1, 2, 3.5]").is_err());
+    assert!(rules("your_symbols = [\"X\", \"Y\", \"Z\"]\nshape_scores = [1, 2, 3000000000]").is_err());
+    assert!(rules("your_symbols = [\"X\", \"Y\", \"Z\"]\noutcome_scores = { lose = 0, draw = 3 }").is_err());
+    assert!(rules("your_symbols = [\"X\", \"Y\", \"Z\"]\noutcome_scores = { lose = 0, draw = 3, win = 6, tie = 1 }").is_err());
+    assert!(rules("your_symbols = [\"X\", \"Y\", \"Z\"]\noutcome_scores = [0, 3, 6]").is_err());
+    assert_eq!(
+      Err(GameError { message: "outcome scores should go up from lose to draw to win, not [6, 3, 0]".to_string() }),
+      rules("your_symbols = [\"X\", \"Y\", \"Z\"]\noutcome_scores = { lose = 6, draw = 3, win = 0 }")
+        .map(|_| ())
+    );
+    assert_eq!(
+      Err(GameError { message: "unknown rule scores".to_string() }),
+      rules("your_symbols = [\"X\", \"Y\", \"Z\"]\nscores = [1, 2, 3]").map(|_| ())
+    );
+    assert!(Game::from_rules("shapes = [").is_err());
+  }
+}
@@ -0,0 +1,278 @@
+use std::cmp::Ordering;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use utils::rng::Rng;
+
+use crate::{Match, Move, ParseMatchError};
+
+const MOVES: [Move; 3] = [Move::Rocks, Move::Paper, Move::Scissors];
+
+// A bot that plays rock paper scissors. It's reset before each game, and then asked for a
+// move every round, given the moves played so far in that game as (yours, theirs).
+pub trait Strategy {
+  fn name(&self) -> String;
+
+  fn next_move(&mut self, history: &[(Move, Move)]) -> Move;
+
+  fn reset(&mut self) {}
+}
+
+// Always plays the same move.
+pub struct Constant(pub Move);
+
+impl Strategy for Constant {
+  fn name(&self) -> String {
+    format!("always {:?}", self.0)
+  }
+
+  fn next_move(&mut self, _: &[(Move, Move)]) -> Move {
+    self.0
+  }
+}
+
+// Plays the moves in order, starting again from the first after the last.
+pub struct Cycle {
+  name: String,
+  moves: Vec<Move>,
+}
+
+impl Cycle {
+  // A cycle named for its moves, or None if there aren't any to play.
+  pub fn new(moves: &[Move]) -> Option<Cycle> {
+    let names: Vec<_> = moves.iter().map(|m| format!("{:?}", m)).collect();
+    Cycle::named(format!("cycle {}", names.join(" ")), moves.to_vec())
+  }
+
+  pub fn named(name: String, moves: Vec<Move>) -> Option<Cycle> {
+    (!moves.is_empty()).then_some(Cycle { name, moves })
+  }
+
+  pub fn moves(&self) -> &[Move] {
+    &self.moves
+  }
+}
+
+impl Strategy for Cycle {
+  fn name(&self) -> String {
+    self.name.clone()
+  }
+
+  fn next_move(&mut self, history: &[(Move, Move)]) -> Move {
+    self.moves[history.len() % self.moves.len()]
+  }
+}
+
+// Plays whatever beats the move the other player has played most so far, starting with
+// rock. Ties go to the first of rock, paper and scissors.
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+  fn name(&self) -> String {
+    "frequency counter".to_string()
+  }
+
+  fn next_move(&mut self, history: &[(Move, Move)]) -> Move {
+    if history.is_empty() {
+      return Move::Rocks;
+    }
+    let count = |m: &Move| history.iter().filter(|(_, theirs)| theirs == m).count();
+    let most = MOVES.iter().rev().max_by_key(|m| count(m)).unwrap();
+    most.get_move(Ordering::Greater)
+  }
+}
+
+// Copies the other player's last move, starting with rock.
+pub struct Mirror;
+
+impl Strategy for Mirror {
+  fn name(&self) -> String {
+    "mirror".to_string()
+  }
+
+  fn next_move(&mut self, history: &[(Move, Move)]) -> Move {
+    history.last().map_or(Move::Rocks, |(_, theirs)| *theirs)
+  }
+}
+
+// Picks moves at random, but the same ones every game for the same seed.
+pub struct Random {
+  seed: u64,
+  rng: Rng,
+}
+
+impl Random {
+  pub fn new(seed: u64) -> Random {
+    Random { seed, rng: Rng::new(seed) }
+  }
+}
+
+impl Strategy for Random {
+  fn name(&self) -> String {
+    format!("random (seed {})", self.seed)
+  }
+
+  fn next_move(&mut self, _: &[(Move, Move)]) -> Move {
+    *self.rng.pick(&MOVES)
+  }
+
+  fn reset(&mut self) {
+    self.rng = Rng::new(self.seed);
+  }
+}
+
+// The strategy guide's two columns as players: their moves, and the moves it says to play.
+pub fn guide_players(lines: std::str::Lines<'_>) -> Result<[Cycle; 2], ParseMatchError> {
+  let mut theirs = vec![];
+  let mut yours = vec![];
+  for line in lines {
+    let mut moves = line.split(' ');
+    for column in [&mut theirs, &mut yours] {
+      let m = moves.next().ok_or(ParseMatchError::ExpectedMove { s: line.to_string() })?;
+      column.push(Move::from_str(m).map_err(|_| ParseMatchError::InvalidMove { s: line.to_string() })?);
+    }
+  }
+  let empty = || ParseMatchError::ExpectedMove { s: String::new() };
+  Ok([
+    Cycle::named("guide (their moves)".to_string(), theirs).ok_or_else(empty)?,
+    Cycle::named("guide (your moves)".to_string(), yours).ok_or_else(empty)?,
+  ])
+}
+
+// How one strategy did over all its games.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+  pub name: String,
+  pub score: i64,
+  // games, each scored by the points from all of its rounds.
+  pub wins: usize,
+  pub draws: usize,
+  pub losses: usize,
+}
+
+// Plays every strategy against every other for `rounds` rounds, scoring each round with
+// Match::score_from_move, and ranks them by total score, then by wins.
+pub fn round_robin(strategies: &mut [Box<dyn Strategy>], rounds: usize) -> Vec<Standing> {
+  let mut standings: Vec<Standing> = strategies
+    .iter()
+    .map(|s| Standing { name: s.name(), score: 0, wins: 0, draws: 0, losses: 0 })
+    .collect();
+  for a in 0..strategies.len() {
+    for b in a + 1..strategies.len() {
+      let (left, right) = strategies.split_at_mut(b);
+      let (score_a, score_b) = play(left[a].as_mut(), right[0].as_mut(), rounds);
+      standings[a].score += score_a;
+      standings[b].score += score_b;
+      match score_a.cmp(&score_b) {
+        Ordering::Greater => (standings[a].wins += 1, standings[b].losses += 1),
+        Ordering::Less => (standings[a].losses += 1, standings[b].wins += 1),
+        Ordering::Equal => (standings[a].draws += 1, standings[b].draws += 1),
+      };
+    }
+  }
+  standings.sort_by_key(|s| (std::cmp::Reverse(s.score), std::cmp::Reverse(s.wins)));
+  standings
+}
+
+// One game, returning each player's total.
+pub fn play(a: &mut dyn Strategy, b: &mut dyn Strategy, rounds: usize) -> (i64, i64) {
+  a.reset();
+  b.reset();
+  let mut history_a = Vec::with_capacity(rounds);
+  let mut history_b = Vec::with_capacity(rounds);
+  let (mut score_a, mut score_b) = (0, 0);
+  for _ in 0..rounds {
+    let (move_a, move_b) = (a.next_move(&history_a), b.next_move(&history_b));
+    // the outcome doesn't matter when scoring the moves.
+    score_a += Match::new(move_b, move_a, Ordering::Equal).score_from_move() as i64;
+    score_b += Match::new(move_a, move_b, Ordering::Equal).score_from_move() as i64;
+    history_a.push((move_a, move_b));
+    history_b.push((move_b, move_a));
+  }
+  (score_a, score_b)
+}
+
+pub fn format_standings(standings: &[Standing]) -> String {
+  let width = standings.iter().map(|s| s.name.len()).max().unwrap_or(0).max("strategy".len());
+  let mut out = String::new();
+  writeln!(out, "rank  {:<width$}  {:>8}  {:>4}  {:>5}  {:>6}", "strategy", "score", "wins", "draws", "losses").unwrap();
+  for (i, s) in standings.iter().enumerate() {
+    writeln!(out, "{:>4}  {:<width$}  {:>8}  {:>4}  {:>5}  {:>6}", i + 1, s.name, s.score, s.wins, s.draws, s.losses)
+      .unwrap();
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::Move::*;
+
+  #[test]
+  pub fn test_strategies() {
+    let mut cycle = Cycle::new(&[Rocks, Paper]).unwrap();
+    assert_eq!(vec![Rocks, Paper, Rocks], (0..3).map(|i| cycle.next_move(&vec![(Rocks, Rocks); i])).collect::<Vec<_>>());
+    assert_eq!(Paper, Mirror.next_move(&[(Rocks, Scissors), (Rocks, Paper)]));
+    assert_eq!(Rocks, Mirror.next_move(&[]));
+    // they've played scissors most, so play rock
+    assert_eq!(Rocks, FrequencyCounter.next_move(&[(Rocks, Scissors), (Rocks, Paper), (Rocks, Scissors)]));
+    assert_eq!(Scissors, FrequencyCounter.next_move(&[(Rocks, Paper)]));
+
+    let mut random = Random::new(7);
+    let first: Vec<_> = (0..20).map(|_| random.next_move(&[])).collect();
+    random.reset();
+    assert_eq!(first, (0..20).map(|_| random.next_move(&[])).collect::<Vec<_>>());
+    assert!(MOVES.iter().all(|m| first.contains(m)));
+    // a cycle needs something to play
+    assert!(Cycle::new(&[]).is_none());
+    assert!(Cycle::named("nothing".to_string(), vec![]).is_none());
+  }
+
+  #[test]
+  pub fn test_play() {
+    // paper beats rock every round: 8 against 1
+    assert_eq!((80, 10), play(&mut Constant(Paper), &mut Constant(Rocks), 10));
+    // the counter loses the first round to paper, then wins the rest with scissors
+    assert_eq!((1 + 9 * 9, 8 + 2 * 9), play(&mut FrequencyCounter, &mut Constant(Paper), 10));
+    // mirror is always a move behind a cycle, and so always loses to it
+    assert_eq!((4 + 8 + 9 + 7, 4 + 1 + 2 + 3), play(&mut Cycle::new(&[Rocks, Paper, Scissors]).unwrap(), &mut Mirror, 4));
+  }
+
+  #[test]
+  pub fn test_round_robin() {
+    let mut strategies: Vec<Box<dyn Strategy>> =
+      vec![Box::new(Constant(Rocks)), Box::new(Constant(Paper)), Box::new(Constant(Scissors))];
+    let standings = round_robin(&mut strategies, 10);
+    // each beats one and loses to another; scissors scores the most for its shape
+    assert_eq!(
+      vec![
+        Standing { name: "always Scissors".to_string(), score: 90 + 30, wins: 1, draws: 0, losses: 1 },
+        Standing { name: "always Paper".to_string(), score: 80 + 20, wins: 1, draws: 0, losses: 1 },
+        Standing { name: "always Rocks".to_string(), score: 70 + 10, wins: 1, draws: 0, losses: 1 },
+      ],
+      standings
+    );
+    assert_eq!(
+"rank  strategy            score  wins  draws  losses
+   1  always Scissors       120     1      0       1
+   2  always Paper          100     1      0       1
+   3  always Rocks           80     1      0       1
+", format_standings(&standings));
+
+    // strategies are reset between games, so results don't depend on the order they're played
+    let mut strategies: Vec<Box<dyn Strategy>> = vec![Box::new(Random::new(3)), Box::new(Mirror), Box::new(Random::new(3))];
+    let standings = round_robin(&mut strategies, 100);
+    let randoms: Vec<_> = standings.iter().filter(|s| s.name == "random (seed 3)").collect();
+    assert_eq!(randoms[0].score, randoms[1].score);
+  }
+
+  #[test]
+  pub fn test_guide_players() {
+    let [theirs, yours] = guide_players("A Y\nB X\nC Z".lines()).unwrap();
+    assert_eq!(&[Rocks, Paper, Scissors], theirs.moves());
+    assert_eq!(&[Paper, Rocks, Scissors], yours.moves());
+    assert!(guide_players("A".lines()).is_err());
+    assert!(guide_players("A Q".lines()).is_err());
+    assert!(guide_players("".lines()).is_err());
+  }
+}
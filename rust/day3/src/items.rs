@@ -0,0 +1,87 @@
+use std::fmt;
+
+use crate::{item, Alphabet};
+
+// A set of items, as a bit per priority: bit 1 is `a`, bit 27 is `A`, and so on. Finding the
+// items two rucksacks share is then a single `&`, rather than hashing every item.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+  // Every item in `items`, or the first one that isn't in the alphabet.
+  pub fn from_items(items: &str, alphabet: Alphabet) -> Result<ItemSet, char> {
+    let mut set = ItemSet::default();
+    for c in items.chars() {
+      set.insert(alphabet.priority(c).ok_or(c)?);
+    }
+    Ok(set)
+  }
+
+  pub fn insert(&mut self, priority: i32) {
+    self.0 |= 1 << priority;
+  }
+
+  pub fn contains(&self, priority: i32) -> bool {
+    self.0 & (1 << priority) != 0
+  }
+
+  pub fn intersection(&self, other: &ItemSet) -> ItemSet {
+    ItemSet(self.0 & other.0)
+  }
+
+  pub fn union(&self, other: &ItemSet) -> ItemSet {
+    ItemSet(self.0 | other.0)
+  }
+
+  pub fn len(&self) -> usize {
+    self.0.count_ones() as usize
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0 == 0
+  }
+
+  // The priority of the only item in the set, if there's exactly one.
+  pub fn single(&self) -> Option<i32> {
+    (self.len() == 1).then(|| self.0.trailing_zeros() as i32)
+  }
+
+  // The priorities in the set, lowest first.
+  pub fn priorities(&self) -> impl Iterator<Item = i32> + '_ {
+    (0..64).filter(|p| self.contains(*p))
+  }
+
+  pub fn items(&self) -> Vec<char> {
+    self.priorities().filter_map(item).collect()
+  }
+}
+
+impl fmt::Debug for ItemSet {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_set().entries(self.priorities()).finish()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_item_set() {
+    let left = ItemSet::from_items("vJrwpWtwJgWr", Alphabet::Letters).unwrap();
+    let right = ItemSet::from_items("hcsFMMfFFhFp", Alphabet::Letters).unwrap();
+    // p is the only item in both halves
+    assert_eq!(Some(16), left.intersection(&right).single());
+    assert_eq!(8, left.len());
+    assert_eq!(8 + 7 - 1, left.union(&right).len());
+    assert!(left.contains(36) && !left.contains(8));
+    assert_eq!(vec![7, 16, 18, 20, 22, 23, 36, 49], left.priorities().collect::<Vec<_>>());
+    assert_eq!("gprtvwJW", left.items().iter().collect::<String>());
+    assert_eq!(None, left.single());
+    assert!(ItemSet::default().is_empty() && ItemSet::default().single().is_none());
+    assert_eq!(Err('1'), ItemSet::from_items("ab1", Alphabet::Letters));
+    let digits = ItemSet::from_items("a09Z", Alphabet::Extended).unwrap();
+    assert_eq!(vec![1, 52, 53, 62], digits.priorities().collect::<Vec<_>>());
+    assert_eq!(vec!['a', 'Z', '0', '9'], digits.items());
+  }
+}
@@ -0,0 +1,322 @@
+use std::fmt;
+use std::str::Lines;
+
+pub mod items;
+
+use items::ItemSet;
+
+// How many elves are in each group for part 2.
+pub const GROUP_SIZE: usize = 3;
+
+// Which characters can be items. The puzzle only uses letters; generated datasets can also
+// use digits, which come after the letters with priorities 53 to 62.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alphabet {
+  #[default]
+  Letters,
+  Extended,
+}
+
+impl Alphabet {
+  pub fn priority(&self, c: char) -> Option<i32> {
+    match (self, c) {
+      (_, 'a'..='z') => Some(c as i32 - 'a' as i32 + 1),
+      (_, 'A'..='Z') => Some(c as i32 - 'A' as i32 + 27),
+      (Alphabet::Extended, '0'..='9') => Some(c as i32 - '0' as i32 + 53),
+      _ => None,
+    }
+  }
+}
+
+pub fn score_duplicate_item(lines: Lines<'_>, alphabet: Alphabet) -> Result<i32, ScoringError> {
+  lines.enumerate().map(|(i, l)| {
+    let [left, right] = compartments(l, i + 1, alphabet)?;
+    single(left.intersection(&right), i + 1, i + 1)
+  }).sum()
+}
+
+pub fn score_groups_unique_elements(content: &str, group_size: usize, alphabet: Alphabet) -> Result<i32, ScoringError> {
+  check_group_size(group_size)?;
+  content.lines().collect::<Vec<_>>().chunks(group_size).enumerate()
+         .map(|(i, lines)| {
+          let first = i * group_size + 1;
+          single(group_items(lines, first, group_size, alphabet)?, first, first + lines.len() - 1)
+         }).sum()
+}
+
+// What was shared by the two compartments of a rucksack, or by a group's rucksacks, for
+// checking the answers by hand. Lines are counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+  pub first_line: usize,
+  pub last_line: usize,
+  // every item they all had (there should be exactly one), or why that couldn't be found.
+  pub shared: Result<Vec<char>, ScoringError>,
+}
+
+impl Explanation {
+  pub fn priority(&self) -> Option<i32> {
+    match &self.shared {
+      // anything shared has already been checked against the alphabet.
+      Ok(items) if items.len() == 1 => Alphabet::Extended.priority(items[0]),
+      _ => None,
+    }
+  }
+}
+
+pub fn explain_duplicate_items(lines: Lines<'_>, alphabet: Alphabet) -> Vec<Explanation> {
+  lines.enumerate().map(|(i, l)| Explanation {
+    first_line: i + 1,
+    last_line: i + 1,
+    shared: compartments(l, i + 1, alphabet).map(|[left, right]| left.intersection(&right).items()),
+  }).collect()
+}
+
+pub fn explain_groups(content: &str, group_size: usize, alphabet: Alphabet) -> Result<Vec<Explanation>, ScoringError> {
+  check_group_size(group_size)?;
+  Ok(content.lines().collect::<Vec<_>>().chunks(group_size).enumerate().map(|(i, lines)| {
+    let first = i * group_size + 1;
+    Explanation {
+      first_line: first,
+      last_line: first + lines.len() - 1,
+      shared: group_items(lines, first, group_size, alphabet).map(|shared| shared.items()),
+    }
+  }).collect())
+}
+
+impl fmt::Display for Explanation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match (&self.shared, self.priority()) {
+      (Ok(items), Some(priority)) => {
+        write!(f, "{}: {} ({})", line_range(self.first_line, self.last_line), items[0], priority)
+      }
+      (Ok(items), None) => {
+        let e = ScoringError::NotSingle { first_line: self.first_line, last_line: self.last_line, shared: items.clone() };
+        write!(f, "{}", e)
+      }
+      (Err(e), _) => write!(f, "{}", e),
+    }
+  }
+}
+
+// A rucksack's two compartments. Every item has to be in the alphabet, and there have to
+// be an even number of them so that the compartments are the same size.
+fn compartments(line: &str, number: usize, alphabet: Alphabet) -> Result<[ItemSet; 2], ScoringError> {
+  if let Some(item) = line.chars().find(|c| alphabet.priority(*c).is_none()) {
+    return Err(ScoringError::InvalidItem { line: number, item });
+  }
+  // every item is ascii now, so bytes are characters.
+  if !line.len().is_multiple_of(2) {
+    return Err(ScoringError::OddLength { line: number, items: line.len() });
+  }
+  let (left, right) = line.split_at(line.len() / 2);
+  let set = |items| ItemSet::from_items(items, alphabet).map_err(|item| ScoringError::InvalidItem { line: number, item });
+  Ok([set(left)?, set(right)?])
+}
+
+// The items in every rucksack of a group starting at line `first`.
+fn group_items(lines: &[&str], first: usize, group_size: usize, alphabet: Alphabet) -> Result<ItemSet, ScoringError> {
+  if lines.len() != group_size {
+    return Err(ScoringError::ShortGroup { first_line: first, last_line: first + lines.len() - 1, group_size });
+  }
+  let mut shared: Option<ItemSet> = None;
+  for (i, line) in lines.iter().enumerate() {
+    let [left, right] = compartments(line, first + i, alphabet)?;
+    let rucksack = left.union(&right);
+    shared = Some(shared.map_or(rucksack, |shared| shared.intersection(&rucksack)));
+  }
+  Ok(shared.unwrap_or_default())
+}
+
+// The priority of the one item shared on lines first to last.
+fn single(shared: ItemSet, first: usize, last: usize) -> Result<i32, ScoringError> {
+  shared.single().ok_or_else(|| ScoringError::NotSingle { first_line: first, last_line: last, shared: shared.items() })
+}
+
+fn check_group_size(group_size: usize) -> Result<(), ScoringError> {
+  if group_size == 0 {
+    return Err(ScoringError::InvalidGroupSize);
+  }
+  Ok(())
+}
+
+fn line_range(first: usize, last: usize) -> String {
+  if first == last { format!("line {}", first) } else { format!("lines {}-{}", first, last) }
+}
+
+// The priority of a letter.
+pub fn score(c: &char) -> Option<i32> {
+  Alphabet::Letters.priority(*c)
+}
+
+// The item with the given priority, in the extended alphabet; the opposite of priority.
+pub fn item(priority: i32) -> Option<char> {
+  match priority {
+    1..=26 => char::from_u32('a' as u32 + priority as u32 - 1),
+    27..=52 => char::from_u32('A' as u32 + priority as u32 - 27),
+    53..=62 => char::from_u32('0' as u32 + priority as u32 - 53),
+    _ => None,
+  }
+}
+
+pub fn print_result(message: &str, r: Result<i32, ScoringError>) {
+  match r {
+    Ok(s) => println!("{} {}", message, s),
+    Err(e) => println!("Error could not calculate: {}: {}", message, e)
+  }
+}
+
+// Lines are counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScoringError {
+  InvalidItem { line: usize, item: char },
+  OddLength { line: usize, items: usize },
+  // what was shared, when it wasn't exactly one item.
+  NotSingle { first_line: usize, last_line: usize, shared: Vec<char> },
+  ShortGroup { first_line: usize, last_line: usize, group_size: usize },
+  InvalidGroupSize,
+}
+
+impl fmt::Display for ScoringError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidItem { line, item } => write!(f, "line {}: not a valid item: {:?}", line, item),
+      Self::OddLength { line, items } => {
+        write!(f, "line {}: {} items can't be split evenly between two compartments", line, items)
+      }
+      Self::NotSingle { first_line, last_line, shared } if shared.is_empty() => {
+        write!(f, "{}: expected a single shared item, found none", line_range(*first_line, *last_line))
+      }
+      Self::NotSingle { first_line, last_line, shared } => {
+        let items: Vec<_> = shared.iter().map(|c| c.to_string()).collect();
+        write!(
+          f, "{}: expected a single shared item, found {}: {}",
+          line_range(*first_line, *last_line), items.len(), items.join(", ")
+        )
+      }
+      Self::ShortGroup { first_line, last_line, group_size } => write!(
+        f, "{}: expected {} elves' rucksacks, but got {}",
+        line_range(*first_line, *last_line), group_size, last_line - first_line + 1
+      ),
+      Self::InvalidGroupSize => write!(f, "groups need at least one elf"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_duplicate_item() {
+    assert_eq!(Ok(157), score_duplicate_item("vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw".lines(), Alphabet::Letters));
+  }
+
+  #[test]
+  pub fn test_score_groups_unique_item() {
+    assert_eq!(Ok(70), score_groups_unique_elements("vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw", GROUP_SIZE, Alphabet::Letters));
+  }
+
+  #[test]
+  pub fn test_invalid_items() {
+    assert_eq!(
+      Err(ScoringError::InvalidItem { line: 2, item: '1' }),
+      score_duplicate_item("abca\na1b1".lines(), Alphabet::Letters)
+    );
+    assert_eq!(
+      Err(ScoringError::InvalidItem { line: 1, item: '!' }),
+      score_groups_unique_elements("!a\n!b\n!c", GROUP_SIZE, Alphabet::Letters)
+    );
+    assert_eq!(
+      Err(ScoringError::InvalidItem { line: 1, item: 'é' }),
+      score_duplicate_item("aébé".lines(), Alphabet::Letters)
+    );
+    assert_eq!(
+      Err(ScoringError::OddLength { line: 1, items: 5 }),
+      score_duplicate_item("abcda".lines(), Alphabet::Letters)
+    );
+    assert_eq!(
+      Err(ScoringError::OddLength { line: 3, items: 3 }),
+      score_groups_unique_elements("abca\nabcd\naaa", GROUP_SIZE, Alphabet::Letters)
+    );
+    assert_eq!(
+      "line 1: 5 items can't be split evenly between two compartments",
+      score_duplicate_item("abcda".lines(), Alphabet::Letters).unwrap_err().to_string()
+    );
+    assert_eq!(
+      "line 2: not a valid item: '1'",
+      score_duplicate_item("abca\na1b1".lines(), Alphabet::Letters).unwrap_err().to_string()
+    );
+  }
+
+  #[test]
+  pub fn test_extended_alphabet() {
+    // 1 is priority 54
+    assert_eq!(Ok(54), score_duplicate_item("a1b1".lines(), Alphabet::Extended));
+    assert_eq!(Ok(62), score_groups_unique_elements("a9\nb9\n9c", GROUP_SIZE, Alphabet::Extended));
+    assert!(score_duplicate_item("a!b!".lines(), Alphabet::Extended).is_err());
+    assert_eq!(Some(53), Alphabet::Extended.priority('0'));
+    assert_eq!(None, Alphabet::Letters.priority('0'));
+    for priority in 1..=62 {
+      assert_eq!(Some(priority), item(priority).and_then(|c| Alphabet::Extended.priority(c)));
+    }
+  }
+
+  #[test]
+  pub fn test_group_size() {
+    let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+    assert_eq!(
+      Err(ScoringError::NotSingle { first_line: 1, last_line: 6, shared: vec![] }),
+      score_groups_unique_elements(input, 6, Alphabet::Letters)
+    );
+    assert_eq!(
+      Err(ScoringError::ShortGroup { first_line: 4, last_line: 5, group_size: 3 }),
+      score_groups_unique_elements(&input[..input.rfind('\n').unwrap()], 3, Alphabet::Letters)
+    );
+    assert_eq!(
+      "lines 4-5: expected 3 elves' rucksacks, but got 2",
+      score_groups_unique_elements(&input[..input.rfind('\n').unwrap()], 3, Alphabet::Letters).unwrap_err().to_string()
+    );
+    assert_eq!(
+      "lines 1-2: expected a single shared item, found 5: f, r, s, F, M",
+      score_groups_unique_elements(input, 2, Alphabet::Letters).unwrap_err().to_string()
+    );
+    assert_eq!(Err(ScoringError::InvalidGroupSize), score_groups_unique_elements(input, 0, Alphabet::Letters));
+    assert!(explain_groups(input, 0, Alphabet::Letters).is_err());
+  }
+
+  #[test]
+  pub fn test_explain() {
+    let explanations = explain_duplicate_items("vJrwpWtwJgWrhcsFMMfFFhFp\nabcd\naabbab".lines(), Alphabet::Letters);
+    assert_eq!(Some(16), explanations[0].priority());
+    assert_eq!("line 1: p (16)", explanations[0].to_string());
+    assert_eq!("line 2: expected a single shared item, found none", explanations[1].to_string());
+    assert_eq!("line 3: expected a single shared item, found 2: a, b", explanations[2].to_string());
+    assert_eq!(None, explanations[2].priority());
+
+    let explanations = explain_groups("abcx\nbcdy\ncdez\nx!\nxy\nxz\nq", GROUP_SIZE, Alphabet::Letters).unwrap();
+    assert_eq!(
+      vec![
+        "lines 1-3: c (3)",
+        "line 4: not a valid item: '!'",
+        "line 7: expected 3 elves' rucksacks, but got 1",
+      ],
+      explanations.iter().map(|e| e.to_string()).collect::<Vec<_>>()
+    );
+  }
+}
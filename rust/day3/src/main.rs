@@ -1,93 +1,43 @@
+use day3::{
+  explain_duplicate_items, explain_groups, print_result, score_duplicate_item, score_groups_unique_elements, Alphabet,
+  GROUP_SIZE,
+};
 use utils::cli::Cli;
-
-use std::{str::Lines, collections::HashSet};
+use utils::config::default_params;
 
 fn main() {
   let args = Cli::parse();
   let content = std::fs::read_to_string(&args.path).expect("could not read file");
-  print_result("score", score_duplicate_item(content.lines()));
-  print_result("score groups", score_groups_unique_elements(&content));
-}
-
-pub fn score_duplicate_item(lines: Lines<'_>) -> Result<i32, ScoringError> {
-  let scores: Result<Vec<_>, _> = lines.map(|l| {
-    let items: Vec<_> = l.chars().collect();
-    let left: HashSet<_> = items[..(items.len() / 2)].iter().collect();
-    let right: HashSet<_> = items[(items.len() / 2) .. (items.len())].iter().collect();
-    let union: Vec<_> = left.intersection(&right).copied().collect();
-    match union.len() {
-      1 => Ok(score(union[0])),
-      _ => Err(ScoringError{ message: "Expected a single duplicate element".to_string() })
+  let mut explain = false;
+  let mut group_size = match default_params(3, &args.path).and_then(|params| params.get("group_size", GROUP_SIZE)) {
+    Ok(group_size) => group_size,
+    Err(e) => return println!("Error reading config: {}", e.message),
+  };
+  let mut alphabet = Alphabet::Letters;
+  let mut rest = args.args.iter();
+  while let Some(arg) = rest.next() {
+    match arg.as_str() {
+      "--explain" => explain = true,
+      "--extended" => alphabet = Alphabet::Extended,
+      "--group-size" => group_size = rest.next().and_then(|n| n.parse().ok()).expect("--group-size needs a number"),
+      arg => {
+        println!("unknown argument {}, expected --explain, --extended or --group-size <n>", arg);
+        return;
+      }
     }
-  }).collect();
-
-  scores.map(|s| s.iter().sum())
-}
-
-pub fn score_groups_unique_elements(content: &str) -> Result<i32, ScoringError> {
-  let scores: Result<Vec<_>, _> = content.lines().collect::<Vec<_>>().chunks(3)
-         .map(|lines| {
-          if lines.len() != 3 {
-            return Err(ScoringError {
-              message: format!("Expected 3 elves rumsacks, but got {}", lines.len()).to_string()
-            });
-          }
-
-          let mut union: HashSet<_> = lines[0].chars().collect();
-          for line in lines {
-            let c = line.chars().collect::<HashSet<_>>();
-            union = union.intersection(&c).copied().collect::<HashSet<_>>();
-          }
-          let unique: Vec<_> = union.iter().collect();
-          match unique.len() {
-            1 => Ok(score(unique[0])),
-            _ => Err(ScoringError{ message: "Expected a single duplicate element".to_string() })
-          }
-         }).collect();
-  scores.map(|s| s.iter().sum())
-}
-
-pub fn score(c: &char) -> i32 {
-  if *c < 'a' {
-    ((*c as u32) - ('A' as u32) + 27) as i32
-  } else {
-    ((*c as u32) - ('a' as u32) + 1) as i32
-  }
-}
-
-pub fn print_result(message: &str, r: Result<i32, ScoringError>) {
-  match r {
-    Ok(s) => println!("{} {}", message, s),
-    Err(e) => println!("Error could not calculate: {}: {}", message, e.message)
-  }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ScoringError {
-  message: String
-}
-
-#[cfg(test)]
-mod tests {
-  use super::*;
-
-  #[test]
-  pub fn test_duplicate_item() {
-    assert_eq!(Ok(157), score_duplicate_item("vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw".lines()));
   }
 
-  #[test]
-  pub fn test_score_groups_unique_item() {
-    assert_eq!(Ok(70), score_groups_unique_elements("vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw"));
+  if explain {
+    println!("rucksacks:");
+    for explanation in explain_duplicate_items(content.lines(), alphabet) {
+      println!("  {}", explanation);
+    }
+    println!("groups of {}:", group_size);
+    match explain_groups(&content, group_size, alphabet) {
+      Ok(explanations) => explanations.iter().for_each(|e| println!("  {}", e)),
+      Err(e) => println!("  {}", e),
+    }
   }
+  print_result("score", score_duplicate_item(content.lines(), alphabet));
+  print_result("score groups", score_groups_unique_elements(&content, group_size, alphabet));
 }
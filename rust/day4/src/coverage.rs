@@ -0,0 +1,209 @@
+use std::cmp::Reverse;
+use std::fmt;
+
+use crate::intervals::IntervalSet;
+use crate::Range;
+
+// How the elves' assignments cover the sections, treating the whole file as one set of
+// assignments rather than as pairs. Elves are numbered from 0 in the order they're listed,
+// so the elves on line n are 2n - 2 and 2n - 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coverage {
+  pub elves: usize,
+  // from the lowest section anyone is assigned to the highest.
+  pub span: Option<Range>,
+  pub covered: IntervalSet,
+  // sections in the span that no elf is assigned to.
+  pub gaps: IntervalSet,
+  // the most elves assigned to any one section, and the sections that many are assigned to.
+  pub max_elves: usize,
+  pub most_covered: IntervalSet,
+  // elves whose sections are all assigned to someone else too. Each could be dropped on its
+  // own, but not necessarily along with the others.
+  pub redundant: Vec<usize>,
+  // the fewest elves that between them still cover every section that's covered.
+  pub minimal_cover: Vec<usize>,
+}
+
+pub fn coverage(assignments: &[Range]) -> Coverage {
+  let covered = IntervalSet::from_ranges(assignments.iter().copied());
+  let span = covered.span();
+  let segments = depths(assignments);
+  let max_elves = segments.iter().map(|s| s.elves).max().unwrap_or(0);
+
+  Coverage {
+    elves: assignments.len(),
+    span,
+    gaps: span.map_or(IntervalSet::default(), |span| covered.gaps(&span)),
+    max_elves,
+    most_covered: IntervalSet::from_ranges(segments.iter().filter(|s| s.elves == max_elves).map(|s| s.range)),
+    redundant: (0..assignments.len()).filter(|e| min_elves(&segments, &assignments[*e]) >= 2).collect(),
+    minimal_cover: minimal_cover(assignments, &covered),
+    covered,
+  }
+}
+
+// A run of sections that all have the same number of elves assigned.
+struct Segment {
+  range: Range,
+  elves: usize,
+}
+
+// Splits the covered sections into segments, in order, by sweeping across the starts and
+// ends of every assignment.
+fn depths(assignments: &[Range]) -> Vec<Segment> {
+  // (section, change in elves), where an assignment stops counting just after its upper.
+  let mut events: Vec<(i64, i64)> = assignments
+    .iter()
+    .filter(|r| !r.is_empty())
+    .flat_map(|r| [(r.lower as i64, 1), (r.upper as i64 + 1, -1)])
+    .collect();
+  events.sort_unstable();
+
+  let mut segments = vec![];
+  let mut elves = 0;
+  for (i, (section, change)) in events.iter().enumerate() {
+    elves += change;
+    match events.get(i + 1) {
+      Some((next, _)) if next > section && elves > 0 => segments.push(Segment {
+        range: Range { lower: *section as i32, upper: (next - 1) as i32 },
+        elves: elves as usize,
+      }),
+      _ => {}
+    }
+  }
+  segments
+}
+
+// The fewest elves assigned to any section of `range`. Empty ranges count as fully covered.
+fn min_elves(segments: &[Segment], range: &Range) -> usize {
+  if range.is_empty() {
+    return usize::MAX;
+  }
+  let first = segments.partition_point(|s| s.range.upper < range.lower);
+  segments[first..].iter().take_while(|s| s.range.lower <= range.upper).map(|s| s.elves).min().unwrap_or(0)
+}
+
+// Covers each run of covered sections from left to right, always picking the elf that
+// reaches furthest from where the cover has got to. For ranges on a line, that greedy choice
+// is never worse than any other.
+fn minimal_cover(assignments: &[Range], covered: &IntervalSet) -> Vec<usize> {
+  let mut order: Vec<usize> = (0..assignments.len()).filter(|e| !assignments[*e].is_empty()).collect();
+  order.sort_by_key(|e| (assignments[*e].lower, Reverse(assignments[*e].upper)));
+
+  let mut cover = vec![];
+  let mut i = 0;
+  for run in covered.ranges() {
+    let mut next = run.lower as i64;
+    while next <= run.upper as i64 {
+      let mut best: Option<usize> = None;
+      while i < order.len() && assignments[order[i]].lower as i64 <= next {
+        if best.is_none_or(|b| assignments[order[i]].upper > assignments[b].upper) {
+          best = Some(order[i]);
+        }
+        i += 1;
+      }
+      // every covered section has an elf starting at or before it.
+      let best = best.expect("covered sections should have an elf");
+      cover.push(best);
+      next = assignments[best].upper as i64 + 1;
+    }
+  }
+  cover.sort_unstable();
+  cover
+}
+
+impl fmt::Display for Coverage {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let ranges = |set: &IntervalSet| {
+      let ranges: Vec<_> = set.ranges().iter().map(|r| r.to_string()).collect();
+      if ranges.is_empty() { "none".to_string() } else { ranges.join(", ") }
+    };
+    // runs of elves next to each other are shown as first-last, since there can be a lot.
+    let elves = |elves: &[usize]| {
+      let mut runs: Vec<(usize, usize)> = vec![];
+      for e in elves {
+        match runs.last_mut() {
+          Some((_, last)) if *last + 1 == *e => *last = *e,
+          _ => runs.push((*e, *e)),
+        }
+      }
+      let runs: Vec<_> = runs
+        .iter()
+        .map(|(first, last)| if first == last { first.to_string() } else { format!("{}-{}", first, last) })
+        .collect();
+      if runs.is_empty() { "none".to_string() } else { runs.join(", ") }
+    };
+    writeln!(f, "elves: {}", self.elves)?;
+    match self.span {
+      Some(span) => writeln!(f, "span: {}", span)?,
+      None => writeln!(f, "span: none")?,
+    }
+    writeln!(f, "sections covered: {}", self.covered.len())?;
+    writeln!(f, "uncovered: {} ({} sections)", ranges(&self.gaps), self.gaps.len())?;
+    writeln!(f, "most covered: {} ({} elves)", ranges(&self.most_covered), self.max_elves)?;
+    writeln!(f, "redundant elves: {} ({} of {})", elves(&self.redundant), self.redundant.len(), self.elves)?;
+    writeln!(f, "minimal cover: {} ({} of {})", elves(&self.minimal_cover), self.minimal_cover.len(), self.elves)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::parse_pairs;
+
+  fn range(lower: i32, upper: i32) -> Range {
+    Range { lower, upper }
+  }
+
+  #[test]
+  pub fn test_coverage() {
+    let assignments = [range(1, 3), range(2, 5), range(4, 4), range(8, 9), range(3, 4)];
+    let coverage = coverage(&assignments);
+    assert_eq!(Some(range(1, 9)), coverage.span);
+    assert_eq!(&[range(1, 5), range(8, 9)], coverage.covered.ranges());
+    assert_eq!(&[range(6, 7)], coverage.gaps.ranges());
+    // 3 and 4 are covered by three elves each
+    assert_eq!(3, coverage.max_elves);
+    assert_eq!(&[range(3, 4)], coverage.most_covered.ranges());
+    assert_eq!(vec![2, 4], coverage.redundant);
+    assert_eq!(vec![0, 1, 3], coverage.minimal_cover);
+    assert_eq!(
+"elves: 5
+span: 1-9
+sections covered: 7
+uncovered: 6-7 (2 sections)
+most covered: 3-4 (3 elves)
+redundant elves: 2, 4 (2 of 5)
+minimal cover: 0-1, 3 (3 of 5)
+", coverage.to_string());
+  }
+
+  #[test]
+  pub fn test_sample_coverage() {
+    let pairs = parse_pairs(include_str!("../../inputs/day4.sample.txt").lines()).unwrap();
+    let assignments: Vec<_> = pairs.iter().flat_map(|(a, b)| [*a, *b]).collect();
+    let coverage = coverage(&assignments);
+    assert_eq!(12, coverage.elves);
+    assert_eq!(Some(range(2, 9)), coverage.span);
+    assert!(coverage.gaps.is_empty());
+    // only 7-9 reaches past 8
+    assert_eq!(vec![5, 6], coverage.minimal_cover);
+    assert!(!coverage.redundant.contains(&5));
+    let kept = IntervalSet::from_ranges(coverage.minimal_cover.iter().map(|e| assignments[*e]));
+    assert_eq!(coverage.covered, kept);
+  }
+
+  #[test]
+  pub fn test_small_coverage() {
+    let coverage = coverage(&[]);
+    assert_eq!((None, 0), (coverage.span, coverage.max_elves));
+    assert!(coverage.gaps.is_empty() && coverage.redundant.is_empty() && coverage.minimal_cover.is_empty());
+
+    // the same assignment twice: either could go, but not both
+    let coverage = super::coverage(&[range(1, 5), range(1, 5)]);
+    assert_eq!(vec![0, 1], coverage.redundant);
+    assert_eq!(vec![0], coverage.minimal_cover);
+    assert_eq!(2, coverage.max_elves);
+  }
+}
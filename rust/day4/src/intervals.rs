@@ -0,0 +1,107 @@
+use crate::Range;
+
+// A set of sections, stored as the fewest ranges that cover them: sorted, and with no two
+// ranges overlapping or next to each other.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+  ranges: Vec<Range>,
+}
+
+impl IntervalSet {
+  // Every section in any of the ranges. Empty ranges add nothing.
+  pub fn from_ranges(ranges: impl IntoIterator<Item = Range>) -> IntervalSet {
+    let mut sorted: Vec<Range> = ranges.into_iter().filter(|r| !r.is_empty()).collect();
+    sorted.sort_by_key(|r| (r.lower, r.upper));
+    let mut merged: Vec<Range> = Vec::with_capacity(sorted.len());
+    for range in sorted {
+      match merged.last_mut() {
+        // widened to i64, since upper + 1 can overflow.
+        Some(last) if range.lower as i64 <= last.upper as i64 + 1 => last.upper = last.upper.max(range.upper),
+        _ => merged.push(range),
+      }
+    }
+    IntervalSet { ranges: merged }
+  }
+
+  pub fn insert(&mut self, range: Range) {
+    *self = IntervalSet::from_ranges(self.ranges.iter().copied().chain([range]));
+  }
+
+  pub fn ranges(&self) -> &[Range] {
+    &self.ranges
+  }
+
+  // The number of sections in the set.
+  pub fn len(&self) -> u64 {
+    self.ranges.iter().map(|r| r.len()).sum()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.ranges.is_empty()
+  }
+
+  // The smallest range holding every section in the set.
+  pub fn span(&self) -> Option<Range> {
+    Some(Range { lower: self.ranges.first()?.lower, upper: self.ranges.last()?.upper })
+  }
+
+  // Whether every section of `range` is in the set.
+  pub fn covers(&self, range: &Range) -> bool {
+    if range.is_empty() {
+      return true;
+    }
+    // the only range that could cover it is the last one starting at or before it.
+    let i = self.ranges.partition_point(|r| r.lower <= range.lower);
+    i > 0 && self.ranges[i - 1].upper >= range.upper
+  }
+
+  // The sections of `within` that aren't in the set.
+  pub fn gaps(&self, within: &Range) -> IntervalSet {
+    let mut gaps = vec![];
+    let mut next = within.lower as i64;
+    for range in &self.ranges {
+      if range.upper < within.lower || range.lower > within.upper {
+        continue;
+      }
+      if (range.lower as i64) > next {
+        gaps.push(Range { lower: next as i32, upper: range.lower - 1 });
+      }
+      next = next.max(range.upper as i64 + 1);
+    }
+    if next <= within.upper as i64 {
+      gaps.push(Range { lower: next as i32, upper: within.upper });
+    }
+    IntervalSet { ranges: gaps }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn range(lower: i32, upper: i32) -> Range {
+    Range { lower, upper }
+  }
+
+  #[test]
+  pub fn test_interval_set() {
+    let mut set = IntervalSet::from_ranges([range(5, 7), range(1, 2), range(3, 3), range(10, 12), range(6, 8), range(4, 2)]);
+    // 1-2 and 3-3 are next to each other, so they merge too
+    assert_eq!(&[range(1, 3), range(5, 8), range(10, 12)], set.ranges());
+    assert_eq!(3 + 4 + 3, set.len());
+    assert_eq!(Some(range(1, 12)), set.span());
+    assert!(set.covers(&range(5, 8)) && set.covers(&range(2, 3)) && set.covers(&range(5, 4)));
+    assert!(!set.covers(&range(3, 5)) && !set.covers(&range(0, 1)) && !set.covers(&range(11, 13)));
+    assert_eq!(&[range(4, 4), range(9, 9)], set.gaps(&range(1, 12)).ranges());
+    assert_eq!(&[range(-2, 0), range(4, 4)], set.gaps(&range(-2, 4)).ranges());
+    assert_eq!(&[range(13, 20)], set.gaps(&range(11, 20)).ranges());
+
+    set.insert(range(4, 9));
+    assert_eq!(&[range(1, 12)], set.ranges());
+    assert!(IntervalSet::default().is_empty() && IntervalSet::default().span().is_none());
+
+    let set = IntervalSet::from_ranges([range(i32::MIN, 0), range(1, i32::MAX)]);
+    assert_eq!(&[range(i32::MIN, i32::MAX)], set.ranges());
+    assert_eq!(1 << 32, set.len());
+  }
+}
@@ -0,0 +1,300 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::{str::Lines};
+use std::str::FromStr;
+
+pub mod coverage;
+pub mod intervals;
+
+pub fn count_fully_contained_pairs(lines: Lines<'_>) -> Result<u32, ParseRangeError> {
+  Ok(count_fully_contained(&parse_pairs(lines)?))
+}
+
+pub fn count_overlapping_pairs(lines: Lines<'_>) -> Result<u32, ParseRangeError> {
+  Ok(count_overlapping(&parse_pairs(lines)?))
+}
+
+// Pairs where one elf's assignment holds all of the other's.
+pub fn count_fully_contained(pairs: &[(Range, Range)]) -> u32 {
+  pairs.iter().filter(|(r1, r2)| r1.fully_contains(r2) || r2.fully_contains(r1)).count() as u32
+}
+
+pub fn count_overlapping(pairs: &[(Range, Range)]) -> u32 {
+  pairs.iter().filter(|(r1, r2)| r1.overlaps(r2)).count() as u32
+}
+
+// Both elves' assignments on each line, stopping at the first line that can't be read.
+pub fn parse_pairs(lines: Lines<'_>) -> Result<Vec<(Range, Range)>, ParseRangeError> {
+  lines.enumerate().map(|(i, line)| parse_pair(line, i + 1)).collect()
+}
+
+// Like parse_pairs, but carries on past lines that can't be read, returning the pairs from
+// every line that could and an error for each line that couldn't.
+pub fn parse_pairs_lenient(lines: Lines<'_>) -> (Vec<(Range, Range)>, Vec<ParseRangeError>) {
+  let mut pairs = vec![];
+  let mut errors = vec![];
+  for (i, line) in lines.enumerate() {
+    match parse_pair(line, i + 1) {
+      Ok(pair) => pairs.push(pair),
+      Err(e) => errors.push(e),
+    }
+  }
+  (pairs, errors)
+}
+
+fn parse_pair(line: &str, number: usize) -> Result<(Range, Range), ParseRangeError> {
+  let parse = || -> Result<(Range, Range), ParseRangeError> {
+    let (r1, r2) = line.split_once(",")
+      .ok_or(ParseRangeError { line: None, message: format!("expected two ranges in: {}", line) })?;
+    Ok((Range::from_str(r1)?, Range::from_str(r2)?))
+  };
+  parse().map_err(|e| ParseRangeError { line: Some(number), ..e })
+}
+
+pub fn print_result(message: &str, r: Result<u32, ParseRangeError>) {
+  match r {
+    Ok(s) => println!("{} {}", message, s),
+    Err(e) => println!("Error could not calculate: {}: {}", message, e)
+  }
+}
+
+// The sections from lower to upper, inclusive. Ranges read from the input always have
+// lower <= upper; any other range is empty, like the intersection of two that don't overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+  pub lower: i32,
+  pub upper: i32
+}
+
+impl Range {
+  // Whether every section of `other` is in this range, which is always true for an empty one.
+  pub fn fully_contains(&self, other: &Range) -> bool {
+    other.is_empty() || (other.lower >= self.lower && other.upper <= self.upper)
+  }
+
+  pub fn contains(&self, point: i32) -> bool {
+    point >= self.lower && point <= self.upper
+  }
+
+  pub fn overlaps(&self, other: &Range) -> bool {
+    !self.is_empty() && !other.is_empty() && (
+      self.contains(other.lower) || self.contains(other.upper)
+        || other.contains(self.lower) || other.contains(self.upper)
+    )
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.lower > self.upper
+  }
+
+  // The number of sections, which for i32::MIN-i32::MAX doesn't fit in a u32.
+  pub fn len(&self) -> u64 {
+    if self.is_empty() { 0 } else { (self.upper as i64 - self.lower as i64 + 1) as u64 }
+  }
+
+  // The sections in both, which is empty if they don't overlap.
+  pub fn intersection(&self, other: &Range) -> Range {
+    Range { lower: self.lower.max(other.lower), upper: self.upper.min(other.upper) }
+  }
+
+  // The sections in either, as one range if they overlap or are next to each other, or two
+  // otherwise, lowest first. Empty ranges are left out.
+  pub fn union(&self, other: &Range) -> Vec<Range> {
+    let mut ranges: Vec<Range> = [*self, *other].into_iter().filter(|r| !r.is_empty()).collect();
+    ranges.sort_by_key(|r| (r.lower, r.upper));
+    if let [first, second] = ranges[..] {
+      // widened to i64, since upper + 1 can overflow.
+      if second.lower as i64 <= first.upper as i64 + 1 {
+        return vec![Range { lower: first.lower, upper: first.upper.max(second.upper) }];
+      }
+    }
+    ranges
+  }
+
+  // The sections in this range but not in `other`: none, the part on one side of it, or the
+  // parts on both sides, lowest first.
+  pub fn difference(&self, other: &Range) -> Vec<Range> {
+    if !self.overlaps(other) {
+      return [*self].into_iter().filter(|r| !r.is_empty()).collect();
+    }
+    let mut ranges = vec![];
+    if self.lower < other.lower {
+      ranges.push(Range { lower: self.lower, upper: other.lower - 1 });
+    }
+    if self.upper > other.upper {
+      ranges.push(Range { lower: other.upper + 1, upper: self.upper });
+    }
+    ranges
+  }
+
+  pub fn iter(&self) -> RangeInclusive<i32> {
+    self.lower..=self.upper
+  }
+}
+
+impl IntoIterator for Range {
+  type Item = i32;
+  type IntoIter = RangeInclusive<i32>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+// The same `a-b` form as the input.
+impl fmt::Display for Range {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}-{}", self.lower, self.upper)
+  }
+}
+
+impl FromStr for Range {
+  type Err = ParseRangeError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    // the dash between the bounds, skipping one at the start for a negative lower bound.
+    let dash = s
+      .char_indices()
+      .skip(1)
+      .find(|(_, c)| *c == '-')
+      .ok_or(ParseRangeError { line: None, message: format!("not a valid range: {}", s) })?
+      .0;
+    let (l, u) = (&s[..dash], &s[dash + 1..]);
+
+    let range = Range {
+      lower: l.parse::<i32>().map_err(|_e| ParseRangeError { line: None, message: format!("not a valid range: {}", s) })?,
+      upper: u.parse::<i32>().map_err(|_e| ParseRangeError { line: None, message: format!("not a valid range: {}", s) })?
+    };
+    if range.is_empty() {
+      return Err(ParseRangeError { line: None, message: format!("range ends before it starts: {}", s) });
+    }
+    Ok(range)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRangeError {
+  // counted from 1, for errors in a line of the input rather than a lone range.
+  pub line: Option<usize>,
+  pub message: String
+}
+
+impl fmt::Display for ParseRangeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.line {
+      Some(line) => write!(f, "line {}: {}", line, self.message),
+      None => write!(f, "{}", self.message),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_count_fully_contained_pairsduplicate_item() {
+    assert_eq!(Ok(2), count_fully_contained_pairs("2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8".lines()));
+  }
+
+  #[test]
+  pub fn test_count_overlapping_pairs() {
+    assert_eq!(Ok(4), count_overlapping_pairs("2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8".lines()));
+  }
+
+  #[test]
+  pub fn test_parse_errors() {
+    let input = "2-4,6-8\n2-3\n5-7,7-9\n2-8,3-x\n6-6,4-6";
+    assert_eq!(
+      Err(ParseRangeError { line: Some(2), message: "expected two ranges in: 2-3".to_string() }),
+      count_overlapping_pairs(input.lines())
+    );
+    assert_eq!("line 2: expected two ranges in: 2-3", parse_pairs(input.lines()).unwrap_err().to_string());
+
+    let (pairs, errors) = parse_pairs_lenient(input.lines());
+    assert_eq!(3, pairs.len());
+    let errors: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+    assert_eq!(vec!["line 2: expected two ranges in: 2-3", "line 4: not a valid range: 3-x"], errors);
+    assert_eq!((1, 2), (count_fully_contained(&pairs), count_overlapping(&pairs)));
+  }
+
+  fn ranges() -> Vec<Range> {
+    // every range, including empty ones, between -3 and 3
+    (-3..=3).flat_map(|lower| (-3..=3).map(move |upper| Range { lower, upper })).collect()
+  }
+
+  #[test]
+  pub fn test_range_algebra() {
+    let r = |s: &str| Range::from_str(s).unwrap();
+    assert_eq!(r("3-5"), r("1-5").intersection(&r("3-7")));
+    assert!(r("1-2").intersection(&r("4-5")).is_empty());
+    // everything contains the empty range, even another empty range
+    let empty = r("1-2").intersection(&r("4-5"));
+    assert!(r("7-9").fully_contains(&empty) && empty.fully_contains(&empty));
+    assert!(!empty.fully_contains(&r("1-1")));
+    assert_eq!(vec![r("1-7")], r("1-5").union(&r("3-7")));
+    assert_eq!(vec![r("1-5")], r("1-2").union(&r("3-5")));
+    assert_eq!(vec![r("1-2"), r("4-5")], r("4-5").union(&r("1-2")));
+    assert_eq!(vec![r("1-2"), r("6-7")], r("1-7").difference(&r("3-5")));
+    assert_eq!(vec![r("1-2")], r("1-5").difference(&r("3-7")));
+    assert!(r("3-5").difference(&r("1-7")).is_empty());
+    assert_eq!(5, r("3-7").len());
+    assert_eq!(vec![3, 4, 5], r("3-5").into_iter().collect::<Vec<_>>());
+    assert_eq!("3-5", r("3-5").to_string());
+    assert_eq!(1 << 32, Range { lower: i32::MIN, upper: i32::MAX }.len());
+    assert_eq!(
+      vec![Range { lower: i32::MIN, upper: i32::MAX }],
+      Range { lower: i32::MIN, upper: 0 }.union(&Range { lower: 1, upper: i32::MAX })
+    );
+  }
+
+  #[test]
+  pub fn test_range_validation() {
+    assert_eq!(
+      Err(ParseRangeError { line: None, message: "range ends before it starts: 5-3".to_string() }),
+      Range::from_str("5-3")
+    );
+    assert!(Range::from_str("3-3").is_ok());
+    assert!(Range::from_str("3").is_err());
+    assert!(Range::from_str("a-3").is_err());
+    assert_eq!(Ok(Range { lower: -3, upper: -1 }), Range::from_str("-3--1"));
+  }
+
+  // Checks the operations against each other and against the sections they contain, for
+  // every pair of small ranges.
+  #[test]
+  pub fn test_range_properties() {
+    for a in ranges() {
+      assert_eq!(a.len(), a.iter().count() as u64);
+      assert_eq!(a.is_empty(), a.iter().next().is_none());
+      if !a.is_empty() {
+        assert_eq!(Ok(a), Range::from_str(&a.to_string()));
+      }
+      for b in ranges() {
+        let (intersection, union, difference) = (a.intersection(&b), a.union(&b), a.difference(&b));
+        assert_eq!(a.overlaps(&b), !intersection.is_empty(), "{:?} {:?}", a, b);
+        assert_eq!(a.overlaps(&b), b.overlaps(&a));
+        assert_eq!(union, b.union(&a));
+        assert_eq!(a.fully_contains(&b), b.difference(&a).is_empty(), "{:?} {:?}", a, b);
+        assert_eq!(a.fully_contains(&b), b.iter().all(|p| a.contains(p)), "{:?} {:?}", a, b);
+        assert_eq!(a.len() + b.len() - intersection.len(), union.iter().map(|r| r.len()).sum::<u64>());
+        assert!(union.len() <= 2 && difference.len() <= 2);
+        for p in -4..=4 {
+          assert_eq!(a.contains(p) && b.contains(p), intersection.contains(p));
+          assert_eq!(a.contains(p) || b.contains(p), union.iter().any(|r| r.contains(p)), "{:?} {:?} {}", a, b, p);
+          assert_eq!(a.contains(p) && !b.contains(p), difference.iter().any(|r| r.contains(p)), "{:?} {:?} {}", a, b, p);
+        }
+      }
+    }
+  }
+}
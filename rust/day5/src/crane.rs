@@ -0,0 +1,126 @@
+use crate::ParseCommandsError;
+
+// A model of crane. Every move lifts the top `amount` crates off the source stack, and the
+// crane decides what order they land in on the destination.
+pub trait CrateMover {
+  fn name(&self) -> String;
+
+  // Rearranges the lifted crates, given bottom first, into the order they land in, bottom
+  // first. Leaving them alone keeps their order, like moving the whole stack at once.
+  fn arrange(&self, lifted: &mut [char]);
+}
+
+// Moves crates one at a time, so they land in reverse.
+pub struct CrateMover9000;
+
+impl CrateMover for CrateMover9000 {
+  fn name(&self) -> String {
+    "CrateMover 9000".to_string()
+  }
+
+  fn arrange(&self, lifted: &mut [char]) {
+    lifted.reverse();
+  }
+}
+
+// Moves all the crates at once, so they keep their order.
+pub struct CrateMover9001;
+
+impl CrateMover for CrateMover9001 {
+  fn name(&self) -> String {
+    "CrateMover 9001".to_string()
+  }
+
+  fn arrange(&self, _: &mut [char]) {}
+}
+
+// Lifts at most `capacity` crates at a time, taking them from the top. Each lift keeps its
+// order, so a capacity of 1 is the 9000 and one at least as big as any move is the 9001.
+pub struct LimitedCapacity(usize);
+
+impl LimitedCapacity {
+  // None for a capacity of 0, which could never lift anything.
+  pub fn new(capacity: usize) -> Option<LimitedCapacity> {
+    (capacity > 0).then_some(LimitedCapacity(capacity))
+  }
+}
+
+impl CrateMover for LimitedCapacity {
+  fn name(&self) -> String {
+    format!("capacity {}", self.0)
+  }
+
+  fn arrange(&self, lifted: &mut [char]) {
+    // reversing the whole batch puts the lifts in the order they land, but each backwards.
+    lifted.reverse();
+    for lift in lifted.chunks_mut(self.0) {
+      lift.reverse();
+    }
+  }
+}
+
+// Moves all the crates at once, but the top `n` crates of the batch swing round to land
+// first, underneath the rest.
+pub struct Rotating(pub usize);
+
+impl CrateMover for Rotating {
+  fn name(&self) -> String {
+    format!("rotate {}", self.0)
+  }
+
+  fn arrange(&self, lifted: &mut [char]) {
+    if !lifted.is_empty() {
+      lifted.rotate_right(self.0 % lifted.len());
+    }
+  }
+}
+
+// The crane with the given name: 9000, 9001, capacity=N or rotate=N.
+pub fn crane(name: &str) -> Result<Box<dyn CrateMover>, ParseCommandsError> {
+  let invalid = || ParseCommandsError { line: None, message: format!("unknown crane {}, expected 9000, 9001, capacity=N or rotate=N", name) };
+  match name.split_once('=') {
+    None if name == "9000" => Ok(Box::new(CrateMover9000)),
+    None if name == "9001" => Ok(Box::new(CrateMover9001)),
+    Some(("capacity", n)) => {
+      let crane = n.parse().ok().and_then(LimitedCapacity::new).ok_or_else(invalid)?;
+      Ok(Box::new(crane))
+    }
+    Some(("rotate", n)) => Ok(Box::new(Rotating(n.parse().map_err(|_e| invalid())?))),
+    _ => Err(invalid()),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn arranged(crane: &dyn CrateMover, lifted: &str) -> String {
+    let mut lifted: Vec<char> = lifted.chars().collect();
+    crane.arrange(&mut lifted);
+    lifted.into_iter().collect()
+  }
+
+  #[test]
+  pub fn test_cranes() {
+    assert_eq!("EDCBA", arranged(&CrateMover9000, "ABCDE"));
+    assert_eq!("ABCDE", arranged(&CrateMover9001, "ABCDE"));
+    // DE goes first, then BC, then A
+    assert_eq!("DEBCA", arranged(&LimitedCapacity::new(2).unwrap(), "ABCDE"));
+    assert_eq!(arranged(&CrateMover9000, "ABCDE"), arranged(&LimitedCapacity::new(1).unwrap(), "ABCDE"));
+    assert_eq!(arranged(&CrateMover9001, "ABCDE"), arranged(&LimitedCapacity::new(5).unwrap(), "ABCDE"));
+    assert_eq!("EABCD", arranged(&Rotating(1), "ABCDE"));
+    assert_eq!("DEABC", arranged(&Rotating(7), "ABCDE"));
+    assert_eq!("", arranged(&Rotating(1), ""));
+    assert!(LimitedCapacity::new(0).is_none());
+  }
+
+  #[test]
+  pub fn test_crane_names() {
+    assert_eq!("CrateMover 9000", crane("9000").unwrap().name());
+    assert_eq!("capacity 3", crane("capacity=3").unwrap().name());
+    assert_eq!("rotate 2", crane("rotate=2").unwrap().name());
+    assert!(crane("capacity=0").is_err());
+    assert!(crane("9002").is_err());
+    assert!(crane("rotate=x").is_err());
+  }
+}
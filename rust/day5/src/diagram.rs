@@ -0,0 +1,154 @@
+use crate::ParseCommandsError;
+
+// Reads a drawing of the stacks, ending with the row of labels, into the crates of each
+// stack from the bottom up. Stack n is drawn in columns 4n - 3 to 4n - 1, as `[A]` or as a
+// gap, and its label is somewhere under it, so there can be any number of stacks. Lines
+// can stop early, since missing columns are gaps; the diagram's first line is `first_line`,
+// for errors.
+pub fn parse_stacks(diagram: &str, first_line: usize) -> Result<Vec<Vec<char>>, ParseCommandsError> {
+  let rows: Vec<Vec<char>> = diagram.lines().map(|row| row.chars().collect()).collect();
+  let (labels, rows) = rows.split_last().ok_or(ParseCommandsError {
+    line: Some(first_line),
+    message: "expected a row of stack labels".to_string(),
+  })?;
+  let n_stacks = parse_labels(labels, first_line + rows.len())?;
+
+  let mut stacks: Vec<Vec<char>> = vec![vec![]; n_stacks];
+  for (i, row) in rows.iter().enumerate().rev() {
+    for (stack, c) in parse_row(row, n_stacks, first_line + i)?.into_iter().enumerate() {
+      if let Some(c) = c {
+        stacks[stack].push(c);
+      }
+    }
+  }
+  Ok(stacks)
+}
+
+// The number of stacks, checking they're labelled 1, 2, 3 and so on, each under its stack.
+fn parse_labels(row: &[char], line: usize) -> Result<usize, ParseCommandsError> {
+  let mut n_stacks = 0;
+  let mut i = 0;
+  while i < row.len() {
+    if row[i].is_whitespace() {
+      i += 1;
+      continue;
+    }
+    let start = i;
+    while i < row.len() && !row[i].is_whitespace() {
+      i += 1;
+    }
+    let label: String = row[start..i].iter().collect();
+    let column = 4 * n_stacks;
+    if label != (n_stacks + 1).to_string() || start < column || i > column + 3 {
+      return Err(ParseCommandsError {
+        line: Some(line),
+        message: format!("expected label {} under stack {}, found {} at column {}", n_stacks + 1, n_stacks + 1, label, start + 1),
+      });
+    }
+    n_stacks += 1;
+  }
+  if n_stacks == 0 {
+    return Err(ParseCommandsError { line: Some(line), message: "expected a row of stack labels".to_string() });
+  }
+  Ok(n_stacks)
+}
+
+// The crate, if any, at this level of each stack.
+fn parse_row(row: &[char], n_stacks: usize, line: usize) -> Result<Vec<Option<char>>, ParseCommandsError> {
+  let at = |i: usize| row.get(i).copied().unwrap_or(' ');
+  let error = |message: String| ParseCommandsError { line: Some(line), message };
+
+  let crates = (0..n_stacks)
+    .map(|stack| match [at(4 * stack), at(4 * stack + 1), at(4 * stack + 2)] {
+      [' ', ' ', ' '] => Ok(None),
+      ['[', c, ']'] if !c.is_whitespace() && c != '[' && c != ']' => Ok(Some(c)),
+      cell => Err(error(format!("expected a crate like [A] or a gap in stack {}, found '{}'", stack + 1, cell.iter().collect::<String>()))),
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+
+  // everything else, between the stacks and past the last, has to be blank.
+  let outside = (0..row.len()).find(|i| (i % 4 == 3 || *i >= 4 * n_stacks) && !row[*i].is_whitespace());
+  if let Some(i) = outside {
+    return Err(error(format!("unexpected '{}' at column {}, outside the {} stacks", row[i], i + 1, n_stacks)));
+  }
+  Ok(crates)
+}
+
+// Draws the stacks the way the puzzle does, as parse_stacks reads them: every line padded
+// to the full width, and each label centred under its stack.
+pub fn format_stacks(stacks: &[Vec<char>]) -> String {
+  let height = stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+  let mut lines: Vec<String> = (0..height)
+    .rev()
+    .map(|level| {
+      let cells: Vec<_> = stacks
+        .iter()
+        .map(|s| s.get(level).map_or("   ".to_string(), |c| format!("[{}]", c)))
+        .collect();
+      cells.join(" ")
+    })
+    .collect();
+  let labels: Vec<_> = (1..=stacks.len()).map(|n| format!("{:^3}", n)).collect();
+  lines.push(labels.join(" "));
+  lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn stacks(stacks: &[&str]) -> Vec<Vec<char>> {
+    stacks.iter().map(|s| s.chars().collect()).collect()
+  }
+
+  #[test]
+  pub fn test_parse_stacks() {
+    let sample = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n";
+    assert_eq!(Ok(stacks(&["ZN", "MCD", "P"])), parse_stacks(sample, 1));
+    // the same with trailing spaces stripped
+    let stripped = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n";
+    assert_eq!(parse_stacks(sample, 1), parse_stacks(stripped, 1));
+    // an empty stack in the middle
+    assert_eq!(Ok(stacks(&["A", "", "B"])), parse_stacks("[A]     [B]\n 1   2   3", 1));
+    assert_eq!(Ok(stacks(&["", ""])), parse_stacks(" 1   2", 1));
+  }
+
+  #[test]
+  pub fn test_many_stacks() {
+    let many: Vec<String> = (0..12).map(|i| ((b'A' + i) as char).to_string()).collect();
+    let many: Vec<&str> = many.iter().map(|s| s.as_str()).collect();
+    let diagram = "[A] [B] [C] [D] [E] [F] [G] [H] [I] [J] [K] [L]
+ 1   2   3   4   5   6   7   8   9  10  11  12
+";
+    assert_eq!(Ok(stacks(&many)), parse_stacks(diagram, 1));
+    // labels can be either side of the middle once they're two digits
+    assert_eq!(Ok(12), parse_labels(&" 1   2   3   4   5   6   7   8   9   10  11  12".chars().collect::<Vec<_>>(), 1));
+  }
+
+  #[test]
+  pub fn test_invalid_diagrams() {
+    let error = |diagram: &str| parse_stacks(diagram, 3).unwrap_err().to_string();
+    assert_eq!("line 3: expected a row of stack labels", error(""));
+    assert_eq!("line 4: expected a row of stack labels", error("[A]\n   "));
+    assert_eq!("line 4: expected label 2 under stack 2, found 3 at column 6", error("[A]\n 1   3"));
+    assert_eq!("line 4: expected label 2 under stack 2, found 2 at column 10", error("[A]\n 1       2"));
+    assert_eq!("line 3: expected a crate like [A] or a gap in stack 1, found '[  '", error("[\n 1"));
+    assert_eq!("line 3: expected a crate like [A] or a gap in stack 1, found '[A['", error("[A[A]\n 1   2"));
+    assert_eq!("line 4: unexpected '[' at column 5, outside the 1 stacks", error("[A]\n[B] [C]\n 1"));
+    assert_eq!("line 3: unexpected 'x' at column 4, outside the 2 stacks", error("[A]x[B]\n 1   2"));
+  }
+
+  #[test]
+  pub fn test_format_stacks() {
+    let sample = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n";
+    assert_eq!(sample, format_stacks(&parse_stacks(sample, 1).unwrap()));
+    assert_eq!("[A]     [B]\n 1   2   3 \n", format_stacks(&stacks(&["A", "", "B"])));
+
+    // round trips, including empty stacks and more than 9
+    for s in [stacks(&["ZN", "MCD", "P"]), stacks(&["", "", ""]), stacks(&["ABCDEFGHIJKL"; 11])] {
+      assert_eq!(Ok(s.clone()), parse_stacks(&format_stacks(&s), 1));
+    }
+    let wide: Vec<Vec<char>> = (0..120).map(|i| vec![(b'A' + (i % 26) as u8) as char; i % 4]).collect();
+    assert_eq!(Ok(wide.clone()), parse_stacks(&format_stacks(&wide), 1));
+  }
+}
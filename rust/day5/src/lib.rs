@@ -0,0 +1,249 @@
+use std::fmt;
+use std::str::FromStr;
+
+pub mod crane;
+pub mod diagram;
+pub mod solver;
+
+use crane::{CrateMover, CrateMover9000, CrateMover9001};
+use diagram::parse_stacks;
+
+pub fn top_of_stacks_9000(content: &str) -> Result<String, ParseCommandsError> {
+  top_of_stacks(content, &CrateMover9000)
+}
+
+pub fn top_of_stacks_9001(content: &str) -> Result<String, ParseCommandsError> {
+  top_of_stacks(content, &CrateMover9001)
+}
+
+// Runs every move command with the given crane, and reads off the top crate of each stack.
+// Stacks that end up empty show as a space, so each letter stays in its stack's place.
+pub fn top_of_stacks(content: &str, crane: &dyn CrateMover) -> Result<String, ParseCommandsError> {
+  let stacks = run(parse_input(content)?, crane, |_, _| {})?;
+  Ok(stacks.iter().map(|s| s.last().copied().unwrap_or(' ')).collect())
+}
+
+// Runs the move commands in order, calling `on_step` with the step number, counting from 1,
+// and the stacks after each one. Each move is checked against the stacks as they are when
+// it's made, and the first that takes more crates than its source stack holds is an error.
+pub fn run(
+  puzzle: Puzzle,
+  crane: &dyn CrateMover,
+  mut on_step: impl FnMut(usize, &[Vec<char>]),
+) -> Result<Vec<Vec<char>>, ParseCommandsError> {
+  let Puzzle { mut stacks, commands } = puzzle;
+  for (step, (line, command)) in commands.iter().enumerate() {
+    // a puzzle that wasn't parsed can have moves to stacks that aren't there.
+    command.check_bounds(&stacks).map_err(|e| ParseCommandsError { line: Some(*line), ..e })?;
+    let held = stacks[command.source as usize].len();
+    if command.amount as usize > held {
+      return Err(ParseCommandsError {
+        line: Some(*line),
+        message: format!("can't {}: stack {} only has {} crates", command, command.source + 1, held)
+      });
+    }
+    apply_move(&mut stacks, command, crane);
+    on_step(step + 1, &stacks);
+  }
+  Ok(stacks)
+}
+
+// Makes one move with the crane. The stacks have to exist, and the source has to hold at
+// least `amount` crates.
+pub fn apply_move(stacks: &mut [Vec<char>], command: &MoveCommand, crane: &dyn CrateMover) {
+  let source = &mut stacks[command.source as usize];
+  // lifting the crates off first means a move onto the same stack puts them back.
+  let mut lifted = source.split_off(source.len() - command.amount as usize);
+  crane.arrange(&mut lifted);
+  stacks[command.destination as usize].extend(lifted);
+}
+
+// The stacks as drawn, from the bottom up, and the move commands, each with its line number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+  pub stacks: Vec<Vec<char>>,
+  pub commands: Vec<(usize, MoveCommand)>,
+}
+
+// Splits the puzzle input at the first blank line into the stacks and the move commands.
+// Blank lines among the commands are skipped.
+pub fn parse_input(content: &str) -> Result<Puzzle, ParseCommandsError> {
+  let lines: Vec<&str> = content.lines().collect();
+  let blank = lines.iter().position(|line| line.trim().is_empty()).ok_or(ParseCommandsError {
+    line: Some(lines.len() + 1),
+    message: "expected a blank line before the move commands".to_string(),
+  })?;
+  let stacks = parse_stacks(&lines[..blank].join("\n"), 1)?;
+
+  let commands = lines
+    .iter()
+    .enumerate()
+    .skip(blank + 1)
+    .filter(|(_, line)| !line.trim().is_empty())
+    .map(|(i, line)| {
+      let at_line = |e: ParseCommandsError| ParseCommandsError { line: Some(i + 1), ..e };
+      let command = MoveCommand::from_str(line).map_err(at_line)?;
+      command.check_bounds(&stacks).map_err(at_line)?;
+      Ok((i + 1, command))
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+
+  Ok(Puzzle { stacks, commands })
+}
+
+pub fn print_result(message: &str, r: Result<String, ParseCommandsError>) {
+  match r {
+    Ok(s) => println!("{} '{}'", message, s),
+    Err(e) => println!("Error could not calculate: {}: {}", message, e)
+  }
+}
+
+// Moves `amount` crates from stack `source` to stack `destination`, counting stacks from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveCommand {
+  pub amount: u32,
+  pub source: u32,
+  pub destination: u32,
+}
+
+impl MoveCommand {
+  fn check_bounds(&self, stacks: &[Vec<char>]) -> Result<(), ParseCommandsError> {
+    let n_stacks = stacks.len() as u32;
+    if self.source >= n_stacks || self.destination >= n_stacks {
+      return Err(ParseCommandsError {
+        line: None,
+        message: format!("can't {}: there are only {} stacks", self, n_stacks)
+      });
+    }
+    Ok(())
+  }
+}
+
+// The command as it's written in the puzzle, with stacks counted from 1.
+impl fmt::Display for MoveCommand {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "move {} from {} to {}", self.amount, self.source + 1, self.destination + 1)
+  }
+}
+
+impl FromStr for MoveCommand {
+  type Err = ParseCommandsError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let invalid = || ParseCommandsError { line: None, message: format!("not a valid move command: {}", s) };
+    let chunks: Vec<_> = s.split_whitespace().collect();
+    match chunks[..] {
+      ["move", amount, "from", source, "to", destination] => {
+        let parse_stack = |label: &str| label
+          .parse::<u32>()
+          .ok()
+          .and_then(|n| n.checked_sub(1))
+          .ok_or_else(invalid);
+        Ok(
+          MoveCommand {
+            amount: amount.parse::<u32>().map_err(|_e| invalid())?,
+            source: parse_stack(source)?,
+            destination: parse_stack(destination)?,
+          }
+        )
+      }
+      _ => Err(invalid())
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCommandsError {
+  // counted from 1, when the error is in a line of the input.
+  pub line: Option<usize>,
+  pub message: String
+}
+
+impl fmt::Display for ParseCommandsError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.line {
+      Some(line) => write!(f, "line {}: {}", line, self.message),
+      None => write!(f, "{}", self.message),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_top_of_stacks() {
+    let input = format!("    [D] {}
+[N] [C] {}
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2", "   ", "   ");
+    assert_eq!(Ok("CMZ".to_string()), top_of_stacks_9000(&input));
+    assert_eq!(Ok("MCD".to_string()), top_of_stacks_9001(&input));
+    assert_eq!(top_of_stacks_9000(&input), top_of_stacks(&input, &crane::LimitedCapacity::new(1).unwrap()));
+    // no move is of more than three crates
+    assert_eq!(Ok("MCD".to_string()), top_of_stacks(&input, &crane::LimitedCapacity::new(3).unwrap()));
+    // ZND lands as ND then Z
+    assert_eq!(Ok("MCZ".to_string()), top_of_stacks(&input, &crane::LimitedCapacity::new(2).unwrap()));
+    assert_eq!(Ok("MCD".to_string()), top_of_stacks(&input, &crane::Rotating(0)));
+  }
+
+  #[test]
+  pub fn test_malformed_input() {
+    assert!(top_of_stacks_9000("[A]\n 1\n\nmove 1 from 1").is_err());
+    assert!(top_of_stacks_9000("[A]\n 1\n\nmove 1 from 0 to 1").is_err());
+    assert!(top_of_stacks_9000("[A]\n 1\n\nmove 1 from 1 to 2").is_err());
+    assert!(top_of_stacks_9001("[A]\n 1\n\nmove 2 from 1 to 1").is_err());
+    assert!(top_of_stacks_9000("[A]").is_err());
+    assert_eq!(Ok("AB".to_string()), top_of_stacks_9000("[A]\n    [B]\n 1   2\n\n"));
+    assert!(top_of_stacks_9000("[\n 1\n\n").is_err());
+    assert_eq!(Ok("A".to_string()), top_of_stacks_9000("[A]\n 1\n\nmove 1 from 1 to 1\n\n"));
+  }
+
+  #[test]
+  pub fn test_error_lines() {
+    let error = |content: &str| top_of_stacks_9000(content).unwrap_err().to_string();
+    assert_eq!("line 5: not a valid move command: move 1 from 1", error("[A]\n 1\n\nmove 1 from 1 to 1\nmove 1 from 1"));
+    assert_eq!(
+      "line 4: can't move 1 from 1 to 2: there are only 1 stacks",
+      error("[A]\n 1\n\nmove 1 from 1 to 2")
+    );
+    assert_eq!(
+      "line 5: can't move 2 from 1 to 1: stack 1 only has 1 crates",
+      error("[A]\n 1\n\nmove 1 from 1 to 1\nmove 2 from 1 to 1")
+    );
+    assert_eq!("line 2: expected a crate like [A] or a gap in stack 1, found 'A  '", error("[A]\nA\n 1\n\n"));
+    assert_eq!("line 3: expected a blank line before the move commands", error("[A]\n 1"));
+  }
+
+  #[test]
+  pub fn test_run() {
+    let puzzle = parse_input(include_str!("../../inputs/day5.sample.txt")).unwrap();
+    let mut steps = vec![];
+    let stacks = run(puzzle.clone(), &CrateMover9000, |step, stacks| steps.push((step, diagram::format_stacks(stacks)))).unwrap();
+    assert_eq!(4, steps.len());
+    assert_eq!((1, "[D]        \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n".to_string()), steps[0]);
+    assert_eq!(diagram::format_stacks(&stacks), steps[3].1);
+    // the second step empties stack 1
+    assert_eq!("        [Z]\n        [N]\n    [C] [D]\n    [M] [P]\n 1   2   3 \n", steps[1].1);
+
+    // stack 2 ends up empty, so it shows as a space
+    assert_eq!(Ok("C ".to_string()), top_of_stacks_9000("    [A]\n[B] [C]\n 1   2\n\nmove 2 from 2 to 1"));
+    assert_eq!(
+      "line 6: can't move 1 from 2 to 1: stack 2 only has 0 crates",
+      top_of_stacks_9001("    [A]\n[B] [C]\n 1   2\n\nmove 2 from 2 to 1\nmove 1 from 2 to 1").unwrap_err().to_string()
+    );
+    assert_eq!("move 3 from 1 to 12", MoveCommand::from_str("move 3 from 1 to 12").unwrap().to_string());
+
+    let puzzle = Puzzle { stacks: vec![vec!['A'], vec![]], commands: vec![(4, MoveCommand { amount: 1, source: 0, destination: 2 })] };
+    assert_eq!(
+      "line 4: can't move 1 from 1 to 3: there are only 2 stacks",
+      run(puzzle, &CrateMover9000, |_, _| {}).unwrap_err().to_string()
+    );
+  }
+}
@@ -1,173 +1,102 @@
+use day5::crane::{crane, CrateMover};
+use day5::diagram::{format_stacks, parse_stacks};
+use day5::solver::{format_solution, solve, MAX_STATES};
+use day5::{parse_input, print_result, run, top_of_stacks, top_of_stacks_9000, top_of_stacks_9001};
 use utils::cli::Cli;
 
-use std::str::FromStr;
-
-fn main() {
-  let args = Cli::parse();
-  let content = std::fs::read_to_string(&args.path).expect("could not read file");
-  print_result("top of stacks 9000", top_of_stacks_9000(&content));
-  print_result("top of stacks 9001", top_of_stacks_9001(&content));
-}
-
-pub fn top_of_stacks_9000(content: &str) -> Result<String, ParseCommandsError> {
-  let (stack_contents, rest) = content.split_once("\n 1").unwrap();
-  let mut stacks = parse_stacks(stack_contents);
-
-  let (_, raw_commands) = rest.split_once("\n\n").unwrap();
-  raw_commands.lines()
-    .for_each(|line| {
-      let command = MoveCommand::from_str(line).unwrap();
-      // println!("moving {:?}", command);
-      for _ in 0..command.amount {
-        let item = stacks[command.source as usize].pop().unwrap();
-        // println!("moving {} from {} to {}", item, command.source, command.destination);
-        stacks[command.destination as usize].push(item);
-      }
-    });
-
-  let s: String = stacks.iter_mut().map(|s| {
-    s.pop().unwrap_or(' ')
-  }).collect();
-  println!("s: {}", s);
-
-  Ok(s)
+const USAGE: &str = "usage: day5 <file> [--crane <9000|9001|capacity=N|rotate=N>] [replay [--step <n>]] \
+[solve <target> [--max-states <n>]]";
 
+struct Options {
+  crane: Option<Box<dyn CrateMover>>,
+  replay: bool,
+  only_step: Option<usize>,
+  target: Option<String>,
+  max_states: usize,
 }
 
-pub fn top_of_stacks_9001(content: &str) -> Result<String, ParseCommandsError> {
-  let (stack_contents, rest) = content.split_once("\n 1").unwrap();
-  let mut stacks = parse_stacks(stack_contents);
-
-  let (_, raw_commands) = rest.split_once("\n\n").unwrap();
-  raw_commands.lines()
-    .for_each(|line| {
-      let command = MoveCommand::from_str(line).unwrap();
-      // println!("moving {:?}", command);
-      // create a temporary package of items in reverse, so that when we copy them
-      // over they appear in the same order.
-      let mut tmp_items = vec![];
-      for _ in 0..command.amount {
-        let item = stacks[command.source as usize].pop().unwrap();
-        tmp_items.push(item);
-      }
-
-      for _ in 0..command.amount {
-        let item = tmp_items.pop().unwrap();
-        // println!("moving {} from {} to {}", item, command.source, command.destination);
-        stacks[command.destination as usize].push(item);
-      }
-    });
-
-  let s: String = stacks.iter_mut().map(|s| {
-    s.pop().unwrap_or(' ')
-  }).collect();
-  println!("s: {}", s);
-
-  // let line = parse_stack_row("[C] [M] [Z]");
-
-  // let s = line.iter().map(|s| s.unwrap_or(' ')).collect::<String>();
-  // println!("s: {}", s);
-
-  Ok(s)
-
+fn parse_args(args: &[String]) -> Result<Options, String> {
+  let mut options = Options { crane: None, replay: false, only_step: None, target: None, max_states: MAX_STATES };
+  let mut rest = args.iter();
+  while let Some(arg) = rest.next() {
+    let mut value = |what: &str| rest.next().ok_or(format!("{} needs {}", arg, what));
+    match arg.as_str() {
+      "--crane" => options.crane = Some(crane(value("a crane")?).map_err(|e| e.message)?),
+      "replay" => options.replay = true,
+      "--step" => options.only_step = Some(value("a number")?.parse().map_err(|_| "--step needs a number")?),
+      "solve" => options.target = Some(value("a file with the target diagram")?.clone()),
+      "--max-states" => options.max_states = value("a number")?.parse().map_err(|_| "--max-states needs a number")?,
+      arg => return Err(format!("unknown argument {}", arg)),
+    }
+  }
+  Ok(options)
 }
 
-fn parse_stacks(stack_contents: &str) -> Vec<Vec<char>> {
-  let mut stacks: Vec<Vec<char>> = vec![];
-  let mut n_stacks = 0;
-
-  stack_contents.lines().for_each(|line| {
-    // println!("parsing stack row: {}", line);
-    let row = parse_stack_row(line);
-    if stacks.is_empty() {
-      n_stacks = parse_stack_row(line).iter().count();
-      // println!(" there are {} stacks in line {}", n_stacks, line);
-      for _ in 0..n_stacks {
-        // println!("pushing empty stack");
-        stacks.push(vec![]);
-      }
+fn main() {
+  let args = Cli::parse();
+  let content = std::fs::read_to_string(&args.path).expect("could not read file");
+  let Options { crane, replay, only_step, target, max_states } = match parse_args(&args.args) {
+    Ok(options) => options,
+    Err(message) => return println!("{}\n{}", message, USAGE),
+  };
+
+  if let Some(target) = target {
+    let target = match std::fs::read_to_string(&target) {
+      Ok(content) => content,
+      Err(e) => return println!("Error: could not read {}: {}", target, e),
+    };
+    let diagram = |content: &str| {
+      // the diagram is everything before the first blank line, if there is one.
+      let lines: Vec<_> = content.lines().take_while(|line| !line.trim().is_empty()).collect();
+      parse_stacks(&lines.join("\n"), 1)
+    };
+    let (start, target) = match (diagram(&content), diagram(&target)) {
+      (Ok(start), Ok(target)) => (start, target),
+      (Err(e), _) => return println!("Error: in the start: {}", e),
+      (_, Err(e)) => return println!("Error: in the target: {}", e),
+    };
+    match solve(&start, &target, crane.as_deref().unwrap_or(&day5::crane::CrateMover9000), max_states) {
+      Ok(moves) => print!("{}", format_solution(&start, &moves)),
+      Err(e) => println!("Error: {}", e.message),
     }
-    for i in 0..n_stacks {
-      // println!("trying to push into stack {}: row {:?}", i, row);
-      if let Some(c) = row[i] {
-        // stacks.get_or_insert(i, vec![]).push(c);
-        stacks[i].push(c);
-      }
+    return;
+  }
+  if replay {
+    replay_moves(&content, crane.as_deref().unwrap_or(&day5::crane::CrateMover9000), only_step);
+    return;
+  }
+  match crane {
+    Some(crane) => print_result(&format!("top of stacks {}", crane.name()), top_of_stacks(&content, crane.as_ref())),
+    None => {
+      print_result("top of stacks 9000", top_of_stacks_9000(&content));
+      print_result("top of stacks 9001", top_of_stacks_9001(&content));
     }
-  });
-
-  stacks
-    .iter_mut()
-    .for_each(|s| s.reverse());
-
-  stacks
-}
-
-fn parse_stack_row(row: &str) -> Vec<Option<char>> {
-  row.chars()
-    .collect::<Vec<_>>()
-    .chunks(4)
-    .map(|s| {
-      match s[0] {
-        '[' => Some(s[1]),
-        _ => None
-      }
-    }).collect()
-}
-
-pub fn print_result(message: &str, r: Result<String, ParseCommandsError>) {
-  match r {
-    Ok(s) => println!("{} '{}'", message, s),
-    Err(e) => println!("Error could not calculate: {}: {}", message, e.message)
   }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct MoveCommand {
-  amount: u32,
-  source: u32,
-  destination: u32,
-}
-
-
-
-impl FromStr for MoveCommand {
-  type Err = ParseCommandsError;
-
-  fn from_str(s: &str) -> Result<Self, Self::Err> {
-    let chunks: Vec<_> = s.split(" ").collect();
-    // TODO: error handling
-    Ok(
-      MoveCommand {
-        amount: chunks[1].parse::<u32>().unwrap(),
-        source: chunks[3].parse::<u32>().unwrap() - 1,
-        destination: chunks[5].parse::<u32>().unwrap() - 1
-      }
-    )
+// Prints the stacks before the first move and after every move, or only after step `only`.
+fn replay_moves(content: &str, crane: &dyn CrateMover, only: Option<usize>) {
+  let puzzle = match parse_input(content) {
+    Ok(puzzle) => puzzle,
+    Err(e) => return println!("Error: {}", e),
+  };
+  if let Some(only) = only.filter(|only| *only == 0 || *only > puzzle.commands.len()) {
+    return println!("Error: there's no step {}, there are {} moves", only, puzzle.commands.len());
   }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ParseCommandsError {
-  message: String
-}
-
-#[cfg(test)]
-mod tests {
-  use super::*;
-
-  #[test]
-  pub fn test_top_of_stacks() {
-    let input = format!("    [D] {}
-[N] [C] {}
-[Z] [M] [P]
- 1   2   3
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2", "   ", "   ");
-    assert_eq!(Ok("CMZ".to_string()), top_of_stacks_9000(&input));
-    assert_eq!(Ok("MCD".to_string()), top_of_stacks_9001(&input));
+  if only.is_none() {
+    print!("start:\n{}", format_stacks(&puzzle.stacks));
+  }
+  let commands = puzzle.commands.clone();
+  let result = run(puzzle, crane, |step, stacks| {
+    if only.is_none_or(|only| only == step) {
+      let (line, command) = commands[step - 1];
+      if only.is_none() {
+        println!();
+      }
+      print!("step {} (line {}): {}\n{}", step, line, command, format_stacks(stacks));
+    }
+  });
+  if let Err(e) = result {
+    println!("\nError: {}", e);
   }
 }
@@ -0,0 +1,145 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::crane::CrateMover;
+use crate::diagram::format_stacks;
+use crate::{apply_move, MoveCommand};
+
+// How many arrangements solve looks at by default before giving up.
+pub const MAX_STATES: usize = 1_000_000;
+
+// Every arrangement seen, with the arrangement and move it was first reached by.
+type Seen = HashMap<Vec<Vec<char>>, Option<(Vec<Vec<char>>, MoveCommand)>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError {
+  pub message: String,
+}
+
+// A shortest list of moves that takes the crane from `start` to `target`, found by a breadth
+// first search over arrangements of the crates. Both need the same stacks and the same
+// crates. Gives up after looking at `max_states` arrangements, and if every arrangement the
+// crane can reach has been looked at, there's no solution.
+pub fn solve(
+  start: &[Vec<char>],
+  target: &[Vec<char>],
+  crane: &dyn CrateMover,
+  max_states: usize,
+) -> Result<Vec<MoveCommand>, SolveError> {
+  if start.len() != target.len() {
+    return Err(SolveError {
+      message: format!("the start has {} stacks but the target has {}", start.len(), target.len()),
+    });
+  }
+  let crates = |stacks: &[Vec<char>]| {
+    let mut crates: Vec<char> = stacks.iter().flatten().copied().collect();
+    crates.sort_unstable();
+    crates
+  };
+  if crates(start) != crates(target) {
+    return Err(SolveError { message: "the start and the target have different crates".to_string() });
+  }
+
+  let mut seen = Seen::new();
+  seen.insert(start.to_vec(), None);
+  let mut queue = VecDeque::from([start.to_vec()]);
+  while let Some(stacks) = queue.pop_front() {
+    if stacks == target {
+      return Ok(path(&seen, &stacks));
+    }
+    for command in moves(&stacks) {
+      if seen.len() >= max_states {
+        return Err(SolveError { message: format!("gave up after {} arrangements", max_states) });
+      }
+      let mut next = stacks.clone();
+      apply_move(&mut next, &command, crane);
+      if !seen.contains_key(&next) {
+        seen.insert(next.clone(), Some((stacks.clone(), command)));
+        queue.push_back(next);
+      }
+    }
+  }
+  Err(SolveError { message: format!("the {} can't reach the target", crane.name()) })
+}
+
+// Every move that can be made: any number of crates from any stack to any other.
+fn moves(stacks: &[Vec<char>]) -> Vec<MoveCommand> {
+  let mut moves = vec![];
+  for (source, stack) in stacks.iter().enumerate() {
+    for destination in (0..stacks.len()).filter(|d| *d != source) {
+      for amount in 1..=stack.len() {
+        moves.push(MoveCommand { amount: amount as u32, source: source as u32, destination: destination as u32 });
+      }
+    }
+  }
+  moves
+}
+
+// The moves that reached `stacks`, first move first.
+fn path(seen: &Seen, stacks: &[Vec<char>]) -> Vec<MoveCommand> {
+  let mut moves = vec![];
+  let mut stacks = stacks.to_vec();
+  while let Some(Some((previous, command))) = seen.get(&stacks) {
+    moves.push(*command);
+    stacks = previous.clone();
+  }
+  moves.reverse();
+  moves
+}
+
+// The start and the moves as a puzzle input, which the crane can be run on to check it
+// ends up at the target.
+pub fn format_solution(start: &[Vec<char>], moves: &[MoveCommand]) -> String {
+  let mut out = format_stacks(start);
+  out.push('\n');
+  for command in moves {
+    out.push_str(&format!("{}\n", command));
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::crane::{CrateMover9000, CrateMover9001, LimitedCapacity};
+  use crate::diagram::parse_stacks;
+  use crate::{parse_input, run};
+
+  fn stacks(stacks: &[&str]) -> Vec<Vec<char>> {
+    stacks.iter().map(|s| s.chars().collect()).collect()
+  }
+
+  // Runs the solution as a puzzle input, to check it really does reach the target.
+  fn check(start: &[Vec<char>], target: &[Vec<char>], crane: &dyn CrateMover) -> usize {
+    let moves = solve(start, target, crane, MAX_STATES).unwrap();
+    let puzzle = parse_input(&format_solution(start, &moves)).unwrap();
+    assert_eq!(target, run(puzzle, crane, |_, _| {}).unwrap());
+    moves.len()
+  }
+
+  #[test]
+  pub fn test_solve() {
+    let sample = parse_stacks("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ", 1).unwrap();
+    // the sample's four moves take the 9000 here, but three are enough
+    let target = stacks(&["C", "M", "PDNZ"]);
+    assert_eq!(3, check(&sample, &target, &CrateMover9000));
+    // the 9001 can't turn a batch over, so it needs an extra move
+    assert_eq!(4, check(&sample, &target, &CrateMover9001));
+    assert_eq!(0, check(&sample, &sample, &CrateMover9000));
+    assert!(check(&sample, &stacks(&["", "", "ZNDMCP"]), &LimitedCapacity::new(2).unwrap()) > 0);
+
+    let moves = solve(&stacks(&["AB", ""]), &stacks(&["", "AB"]), &CrateMover9001, MAX_STATES).unwrap();
+    assert_eq!("[B]    \n[A]    \n 1   2 \n\nmove 2 from 1 to 2\n", format_solution(&stacks(&["AB", ""]), &moves));
+  }
+
+  #[test]
+  pub fn test_unsolvable() {
+    let error = |start: &[&str], target: &[&str], max_states: usize| {
+      solve(&stacks(start), &stacks(target), &CrateMover9000, max_states).unwrap_err().message
+    };
+    assert_eq!("the start has 2 stacks but the target has 3", error(&["A", ""], &["A", "", ""], MAX_STATES));
+    assert_eq!("the start and the target have different crates", error(&["A", ""], &["", "B"], MAX_STATES));
+    // with only two stacks, moving one crate at a time can never swap them round
+    assert_eq!("the CrateMover 9000 can't reach the target", error(&["AB", ""], &["BA", ""], MAX_STATES));
+    assert_eq!("gave up after 3 arrangements", error(&["ABC", "", ""], &["", "", "ABC"], 3));
+  }
+}
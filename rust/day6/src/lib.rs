@@ -0,0 +1,184 @@
+use std::collections::{HashSet, VecDeque};
+use std::ops::ControlFlow;
+
+use utils::config::{ConfigError, Params};
+
+pub mod stream;
+
+// The puzzle's marker sizes, for the start of a packet and the start of a message.
+pub const PACKET_MARKER: usize = 4;
+pub const MESSAGE_MARKER: usize = 14;
+
+// The last `size` bytes of a signal, with a count of each byte in them and of how many bytes
+// are in there more than once, so that sliding along a byte is O(1) for any size.
+#[derive(Debug, Clone)]
+pub struct Window {
+  size: usize,
+  bytes: VecDeque<u8>,
+  counts: [u32; 256],
+  // bytes in the window that have already been seen earlier in it.
+  duplicates: usize,
+}
+
+impl Window {
+  pub fn new(size: usize) -> Window {
+    // the window only grows as bytes arrive, since `size` can be far bigger than the signal.
+    Window { size, bytes: VecDeque::new(), counts: [0; 256], duplicates: 0 }
+  }
+
+  pub fn size(&self) -> usize {
+    self.size
+  }
+
+  // Slides the window along to the next byte, and returns whether it's now a marker: full,
+  // with every byte different.
+  pub fn push(&mut self, b: u8) -> bool {
+    self.bytes.push_back(b);
+    self.counts[b as usize] += 1;
+    if self.counts[b as usize] > 1 {
+      self.duplicates += 1;
+    }
+    if self.bytes.len() > self.size {
+      let old = self.bytes.pop_front().unwrap();
+      self.counts[old as usize] -= 1;
+      if self.counts[old as usize] > 0 {
+        self.duplicates -= 1;
+      }
+    }
+    self.bytes.len() == self.size && self.duplicates == 0
+  }
+}
+
+// How many bytes have been read when the last `n` are first all different, or None if
+// they never are. The puzzle's signals are ASCII, so that's the same as characters for
+// them. A marker of 0 bytes is there before anything's been read.
+pub fn find_marker(content: &str, n: usize) -> Option<usize> {
+  find_markers(content, &[n])[0]
+}
+
+// The packet and message marker sizes for an input, from its parameters (see utils::config).
+pub fn marker_sizes(params: &Params) -> Result<[usize; 2], ConfigError> {
+  Ok([params.get("packet_marker", PACKET_MARKER)?, params.get("message_marker", MESSAGE_MARKER)?])
+}
+
+// find_marker for each of the sizes, in a single pass over the signal.
+pub fn find_markers(content: &str, sizes: &[usize]) -> Vec<Option<usize>> {
+  let mut found: Vec<Option<usize>> = sizes.iter().map(|n| (*n == 0).then_some(0)).collect();
+  let mut windows: Vec<(usize, Window)> =
+    sizes.iter().enumerate().filter(|(_, n)| **n > 0).map(|(i, n)| (i, Window::new(*n))).collect();
+  for (i, b) in content.bytes().enumerate() {
+    if windows.is_empty() {
+      break;
+    }
+    windows.retain_mut(|(marker, window)| {
+      if window.push(b) {
+        found[*marker] = Some(i + 1);
+        return false;
+      }
+      true
+    });
+  }
+  found
+}
+
+pub fn first_unique_chars(content: &str, n: usize) -> usize {
+  let mut seen: VecDeque<char> = VecDeque::new();
+  for (i, c) in content.chars().enumerate() {
+    seen.push_back(c);
+    if seen.iter().copied().collect::<HashSet<_>>().len() == n {
+      return i + 1;
+    }
+    if seen.len() == n {
+      seen.pop_front();
+    }
+  }
+  0
+}
+
+pub fn first_unique_chars_with_try_for_each(content: &str, n: usize) -> usize {
+  if n == 0 || content.chars().count() < n {
+    return 0;
+  }
+  let mut last_n: VecDeque<char> = content.chars().take(n - 1).collect();
+  let mut count = n - 1;
+  let found = content.chars().skip(n - 1).try_for_each(|c| {
+    last_n.push_back(c);
+    count += 1;
+    let s: HashSet<char> = last_n.iter().copied().collect();
+    if s.len() == n {
+      return ControlFlow::Break(());
+    }
+    last_n.pop_front();
+    ControlFlow::Continue(())
+  });
+
+  if found.is_break() { count } else { 0 }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_start_of_packet() {
+    assert_eq!(7, first_unique_chars("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4));
+    assert_eq!(19, first_unique_chars("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14));
+    assert_eq!(23, first_unique_chars("bvwbjplbgvbhsrlpgdmjqwftvncz", 14));
+    assert_eq!(23, first_unique_chars("nppdvjthqldpwncqszvftbrmjlhg", 14));
+    assert_eq!(29, first_unique_chars("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14));
+    assert_eq!(26, first_unique_chars("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14));
+  }
+
+  #[test]
+  pub fn test_find_marker() {
+    let signals = [
+      "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+      "bvwbjplbgvbhsrlpgdmjqwftvncz",
+      "nppdvjthqldpwncqszvftbrmjlhg",
+      "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+      "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+    ];
+    for signal in signals {
+      for n in 1..=20 {
+        assert_eq!(Some(first_unique_chars(signal, n)).filter(|i| *i > 0), find_marker(signal, n));
+      }
+    }
+    assert_eq!(vec![Some(7), Some(19), None, Some(0)], find_markers(signals[0], &[4, 14, 30, 0]));
+    assert_eq!(None, find_marker("abc", 4));
+    assert_eq!(Some(3), find_marker("abc", 3));
+    assert_eq!(None, find_marker("aaaaa", 2));
+    assert_eq!(Some(1), find_marker("a", 1));
+    assert_eq!(Some(0), find_marker("", 0));
+    // every byte of the alphabet is different, right up to the biggest window
+    let alphabet: String = (b'!'..=b'~').map(|b| b as char).collect();
+    assert_eq!(Some(alphabet.len()), find_marker(&format!("{}{}", alphabet, alphabet), alphabet.len()));
+    assert_eq!(None, find_marker(&alphabet, alphabet.len() + 1));
+    // a window far too big to allocate up front is never full
+    assert_eq!(vec![None, None, Some(3)], find_markers("abc", &[1_000_000_000_000, usize::MAX, 3]));
+  }
+
+  #[test]
+  pub fn test_marker_sizes() {
+    let config = utils::config::Config::parse("[day6.\"day6.sample.txt\"]\nmessage_marker = 5").unwrap();
+    let signal = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+    let sizes = marker_sizes(&config.params(6, "day6.txt")).unwrap();
+    assert_eq!(vec![Some(7), Some(19)], find_markers(signal, &sizes));
+    let sizes = marker_sizes(&config.params(6, "day6.sample.txt")).unwrap();
+    assert_eq!(vec![Some(7), Some(8)], find_markers(signal, &sizes));
+  }
+
+  #[test]
+  pub fn test_window() {
+    let mut window = Window::new(3);
+    let markers: Vec<_> = "abcabbcd".bytes().map(|b| window.push(b)).collect();
+    assert_eq!(vec![false, false, true, true, true, false, false, true], markers);
+  }
+
+  #[test]
+  pub fn test_try_for_each_short_input() {
+    assert_eq!(7, first_unique_chars_with_try_for_each("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4));
+    assert_eq!(0, first_unique_chars_with_try_for_each("abc", 4));
+    assert_eq!(0, first_unique_chars_with_try_for_each("é", 2));
+    assert_eq!(0, first_unique_chars_with_try_for_each("aaaaa", 2));
+  }
+}
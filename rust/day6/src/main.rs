@@ -1,59 +1,65 @@
+use std::io::Read;
+use std::net::TcpStream;
+
+use day6::{find_markers, marker_sizes};
+use day6::stream::markers;
 use utils::cli::Cli;
+use utils::config::default_params;
 
-use std::collections::{HashSet, VecDeque};
-use std::ops::ControlFlow;
+const USAGE: &str = "expected marker sizes, like 4 14, or --stream <size>";
 
 fn main() {
   let args = Cli::parse();
+  if args.args.first().is_some_and(|a| a == "--stream") {
+    return match &args.args[1..] {
+      [size] => match size.parse() {
+        Ok(size) => stream(&args.path.to_string_lossy(), size),
+        Err(_) => println!("{}", USAGE),
+      },
+      _ => println!("{}", USAGE),
+    };
+  }
+
   let content = std::fs::read_to_string(&args.path).expect("could not read file");
-  println!("start of packet: {}", first_unique_chars(&content, 4));
-  println!("start of message: {}", first_unique_chars(&content, 14));
+  if args.args.is_empty() {
+    let sizes = match default_params(6, &args.path).and_then(|params| marker_sizes(&params)) {
+      Ok(sizes) => sizes,
+      Err(e) => return println!("Error reading config: {}", e.message),
+    };
+    let markers = find_markers(&content, &sizes);
+    print_marker("start of packet", markers[0]);
+    print_marker("start of message", markers[1]);
+    return;
+  }
+  let sizes: Vec<usize> = match args.args.iter().map(|a| a.parse()).collect() {
+    Ok(sizes) => sizes,
+    Err(_) => return println!("{}", USAGE),
+  };
+  for (size, marker) in sizes.iter().zip(find_markers(&content, &sizes)) {
+    print_marker(&format!("marker of {}", size), marker);
+  }
 }
 
-pub fn first_unique_chars(content: &str, n: usize) -> usize {
-  let mut seen: VecDeque<char> = VecDeque::new();
-  for (i, c) in content.chars().enumerate() {
-    seen.push_back(c);
-    if seen.iter().copied().collect::<HashSet<_>>().len() == n {
-      return i + 1;
-    }
-    if seen.len() == n {
-      seen.pop_front();
-    }
+fn print_marker(message: &str, marker: Option<usize>) {
+  match marker {
+    Some(i) => println!("{}: {}", message, i),
+    None => println!("{}: not found", message),
   }
-  return 0;
 }
 
-pub fn first_unique_chars_with_try_for_each(content: &str, n: usize) -> usize {
-  let mut last_n: VecDeque<char> = VecDeque::from(
-    content[0..n].chars().collect::<Vec<char>>()
-  );
-  let mut count = n - 1;
-  content.chars().skip(n - 1).try_for_each(|c| {
-    last_n.push_back(c);
-    count = count + 1;
-    let s: HashSet<char> = last_n.iter().copied().collect();
-    if s.len() == n {
-      return ControlFlow::Break(());
+// Prints every marker as it arrives, from a file, stdin for `-`, or a socket for tcp:HOST:PORT.
+fn stream(source: &str, size: usize) {
+  let reader: Box<dyn Read> = match source {
+    "-" => Box::new(std::io::stdin()),
+    _ => match source.strip_prefix("tcp:") {
+      Some(address) => Box::new(TcpStream::connect(address).expect("could not connect")),
+      None => Box::new(std::fs::File::open(source).expect("could not read file")),
+    },
+  };
+  for marker in markers(reader, size) {
+    match marker {
+      Ok(i) => println!("{}", i),
+      Err(e) => return println!("Error reading signal: {}", e),
     }
-    last_n.pop_front();
-    return ControlFlow::Continue(());
-  });
-
-  count
-}
-
-#[cfg(test)]
-mod tests {
-  use super::*;
-
-  #[test]
-  pub fn test_start_of_packet() {
-    assert_eq!(7, first_unique_chars("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4));
-    assert_eq!(19, first_unique_chars("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14));
-    assert_eq!(23, first_unique_chars("bvwbjplbgvbhsrlpgdmjqwftvncz", 14));
-    assert_eq!(23, first_unique_chars("nppdvjthqldpwncqszvftbrmjlhg", 14));
-    assert_eq!(29, first_unique_chars("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14));
-    assert_eq!(26, first_unique_chars("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14));
   }
 }
@@ -0,0 +1,115 @@
+use std::io::{self, BufReader, Bytes, Read};
+
+use crate::Window;
+
+// Every marker of `size` bytes in a signal read from `reader`, as the number of bytes read
+// when each one ends, found as the bytes arrive rather than after reading the whole signal.
+// So as well as the first marker there's one for every later byte that ends `size` bytes
+// that are all different. Like find_marker, a marker of 0 bytes is at the very start, and
+// then after every byte.
+pub struct Markers<R: Read> {
+  bytes: Bytes<BufReader<R>>,
+  window: Window,
+  read: usize,
+  // whether the marker at 0 bytes is still to come, for markers of 0 bytes.
+  at_start: bool,
+  // set after an error, so nothing more is read.
+  done: bool,
+}
+
+pub fn markers<R: Read>(reader: R, size: usize) -> Markers<R> {
+  Markers { bytes: BufReader::new(reader).bytes(), window: Window::new(size), read: 0, at_start: size == 0, done: false }
+}
+
+impl<R: Read> Iterator for Markers<R> {
+  type Item = io::Result<usize>;
+
+  // Reads until the next marker, and stops at the end of the signal or the first error.
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.at_start {
+      self.at_start = false;
+      return Some(Ok(0));
+    }
+    if self.done {
+      return None;
+    }
+    loop {
+      match self.bytes.next()? {
+        Ok(b) => {
+          self.read += 1;
+          if self.window.push(b) {
+            return Some(Ok(self.read));
+          }
+        }
+        Err(e) => {
+          self.done = true;
+          return Some(Err(e));
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::find_marker;
+  use std::io::{Cursor, Write};
+  use std::net::{TcpListener, TcpStream};
+
+  fn all(signal: &str, size: usize) -> Vec<usize> {
+    markers(Cursor::new(signal), size).collect::<io::Result<_>>().unwrap()
+  }
+
+  #[test]
+  pub fn test_markers() {
+    assert_eq!(vec![3, 4, 5, 8], all("abcabbcd", 3));
+    assert_eq!(Vec::<usize>::new(), all("ab", 3));
+    assert_eq!(Vec::<usize>::new(), all("", 1));
+    assert_eq!(vec![0, 1, 2], all("ab", 0));
+    assert_eq!(Vec::<usize>::new(), all("abc", usize::MAX));
+    let signal = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+    for size in 0..20 {
+      assert_eq!(find_marker(signal, size), all(signal, size).first().copied());
+    }
+  }
+
+  // A reader that hands over one byte at a time and then fails, like a device that drops.
+  struct Flaky(Vec<u8>);
+
+  impl Read for Flaky {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+      if self.0.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::ConnectionReset, "gone"));
+      }
+      buf[0] = self.0.remove(0);
+      Ok(1)
+    }
+  }
+
+  #[test]
+  pub fn test_read_errors() {
+    let mut markers = markers(Flaky(b"abcd".to_vec()), 2);
+    assert_eq!(vec![2, 3, 4], markers.by_ref().take(3).map(|m| m.unwrap()).collect::<Vec<_>>());
+    assert_eq!(io::ErrorKind::ConnectionReset, markers.next().unwrap().unwrap_err().kind());
+    assert!(markers.next().is_none());
+  }
+
+  #[test]
+  pub fn test_tcp_stream() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let sender = std::thread::spawn(move || {
+      let (mut socket, _) = listener.accept().unwrap();
+      // in pieces, with a marker split across them.
+      for piece in ["mjqj", "pqmgb", "ljsphdztnvjfqwrcgsmlb"] {
+        socket.write_all(piece.as_bytes()).unwrap();
+        socket.flush().unwrap();
+      }
+    });
+    let found: Vec<usize> = markers(TcpStream::connect(address).unwrap(), 14).map(|m| m.unwrap()).collect();
+    sender.join().unwrap();
+    assert_eq!(vec![19, 25, 26, 27, 28, 29, 30], found);
+    assert_eq!(all("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14), found);
+  }
+}
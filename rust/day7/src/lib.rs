@@ -0,0 +1,237 @@
+use std::{str::Lines, iter::Peekable, any::Any};
+
+// The puzzle's sizes, for part1's small directories and for the disk in part2.
+pub const MAX_DIR_SIZE: usize = 100000;
+pub const DISK_SIZE: usize = 70000000;
+pub const NEEDED_SPACE: usize = 30000000;
+
+pub fn part1(tree: &Dir, max_dir_size: usize) -> Result<usize, ParseTreeError> {
+  let sizes = calculate_dir_sizes(tree)?;
+
+  Ok(
+    sizes
+      .iter()
+      .filter_map(|s| {
+        if s.1 < max_dir_size { Some(s.1) } else { None }
+      })
+      .sum()
+  )
+}
+
+pub fn part2(tree: &Dir, disk_size: usize, needed_space: usize) -> Result<(String, usize), ParseTreeError> {
+  let mut sizes = calculate_dir_sizes(tree)?;
+  let root_size = *sizes.last().map(|(_, size)| size).unwrap_or(&0);
+
+  let remaining_space = disk_size.checked_sub(root_size).ok_or(ParseTreeError {
+    message: format!("used space {} is larger than the disk", root_size)
+  })?;
+  // println!("remaining space: {}. Need {}", remaining_space, needed_space - remaining_space);
+
+  sizes.sort_by_key(|(_, size)| *size);
+  let smallest_dir_big_enough = sizes
+    .into_iter()
+    // .inspect(|(name, size)| {
+    //   println!("trying {} {}", name, size);
+    // })
+    .find(|(_, size)| {
+      needed_space < remaining_space + size
+    });
+  smallest_dir_big_enough.ok_or(ParseTreeError {
+    message: "no directory is big enough to free the needed space".to_string()
+  })
+}
+
+fn calculate_dir_sizes(tree: &Dir) -> Result<Vec<(String, usize)>, ParseTreeError> {
+  let mut sizes = vec![];
+  let mut size: usize = 0;
+  for entry in &tree.entries {
+    let entry_size = if let Some(dir) = entry.as_ref().as_any().downcast_ref::<Dir>() {
+      let mut dir_sizes = calculate_dir_sizes(dir)?;
+      let dir_size = *dir_sizes.last().map(|(_, size)| size).unwrap_or(&0);
+      // println!("Adding {} with size {}", entry.get_name(), dir_size);
+      sizes.append(&mut dir_sizes);
+      dir_size
+    } else {
+      entry.get_size()
+    };
+    size = size.checked_add(entry_size).ok_or(ParseTreeError {
+      message: format!("size of {} is too large", tree.get_name())
+    })?;
+  }
+
+  sizes.push((tree.get_name().to_string(), size));
+  Ok(sizes)
+}
+
+pub fn build_tree(lines: &mut Peekable<Lines<'_>>) -> Result<Dir, ParseTreeError> {
+  let mut dir = Dir {
+    name: {
+      let line = lines.next().unwrap_or_default();
+      match line.split(' ').collect::<Vec<_>>()[..] {
+        ["$", "cd", name] => name.to_string(),
+        _ => return Err(ParseTreeError { message: format!("expected a cd command: {}", line) })
+      }
+    },
+    entries: vec![],
+  };
+  while let Some(peek) = lines.peek() {
+    let (command, args) = match peek.split(' ').collect::<Vec<_>>()[..] {
+      ["$", command] => (command, None),
+      ["$", command, args] => (command, Some(args)),
+      _ => return Err(ParseTreeError { message: format!("expected a command: {}", peek) })
+    };
+    match command {
+      "cd" if Some("..") == args => {
+        lines.next();
+        break;
+      }
+      "cd" if args.is_some() => {
+        dir.entries.push(Box::from(build_tree(lines)?));
+      }
+      "ls" if args.is_none() => {
+        parse_ls(lines)?.into_iter().for_each(|l| {
+          dir.entries.push(l);
+        });
+      }
+      _ => return Err(ParseTreeError { message: format!("unrecognized command: {}", peek) })
+    }
+  }
+
+  // println!("built tree: {}", dir.get_name());
+
+  Ok(dir)
+}
+
+fn parse_ls(lines: &mut Peekable<Lines<'_>>) -> Result<Vec<Box<dyn DirEntry>>, ParseTreeError> {
+  let mut entries: Vec<Box<dyn DirEntry>> = vec![];
+  lines.next();
+  while let Some(line) = lines.next_if(|s| !s.starts_with('$')) {
+    // println!("parsing {line}");
+    let parts = line.split_once(' ')
+      .ok_or(ParseTreeError { message: format!("expected a directory entry: {}", line) })?;
+    match parts.0 {
+      "dir" => (),
+      _ => {
+        entries.push(Box::new(File {
+          name: parts.1.to_string(),
+          size: parts.0.parse::<usize>()
+            .map_err(|_e| ParseTreeError { message: format!("not a valid file size: {}", line) })?,
+        }));
+      }
+    }
+  }
+
+  Ok(entries)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTreeError {
+  pub message: String
+}
+
+// A special trait that allows us to convert to Any, so that we can downcast.
+pub trait AToAny: 'static {
+  fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: 'static> AToAny for T {
+  fn as_any(&self) -> &dyn Any {
+      self
+  }
+}
+
+#[derive(Debug)]
+struct File {
+  name: String,
+  size: usize
+}
+
+pub trait DirEntry: AToAny {
+  fn get_name(&self) -> &str;
+  fn get_size(&self) -> usize;
+  fn is_dir(&self) -> bool;
+}
+
+pub struct Dir {
+  name: String,
+  entries: Vec<Box<dyn DirEntry>>,
+}
+
+impl DirEntry for File {
+  fn get_size(&self) -> usize {
+    self.size
+  }
+  fn get_name(&self) -> &str {
+    &self.name
+  }
+  fn is_dir(&self) -> bool {
+    false
+  }
+}
+
+impl DirEntry for Dir {
+  fn get_size(&self) -> usize {
+    self.entries
+      .iter()
+      .map(|e| e.get_size()).sum()
+  }
+  fn get_name(&self) -> &str {
+    &self.name
+  }
+  fn is_dir(&self) -> bool {
+    true
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_get_dirs_under_100k() {
+    let root = build_tree(&mut "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k".lines().peekable()).unwrap();
+    assert_eq!(Ok(95437), part1(&root, MAX_DIR_SIZE));
+    assert_eq!(Ok(("d".to_string(), 24933642)), part2(&root, DISK_SIZE, NEEDED_SPACE));
+    // a bigger disk and a smaller limit
+    assert_eq!(Ok(584), part1(&root, 1000));
+    assert_eq!(Ok(("e".to_string(), 584)), part2(&root, 100000000, NEEDED_SPACE));
+  }
+
+  #[test]
+  pub fn test_malformed_output() {
+    assert!(build_tree(&mut "".lines().peekable()).is_err());
+    assert!(build_tree(&mut "$ cd".lines().peekable()).is_err());
+    assert!(build_tree(&mut "$ cd /\nfoo".lines().peekable()).is_err());
+    assert!(build_tree(&mut "$ cd /\n$ rm -rf".lines().peekable()).is_err());
+    assert!(build_tree(&mut "$ cd /\n$ ls\nabc".lines().peekable()).is_err());
+    assert!(build_tree(&mut "$ cd /\n$ ls\nabc d".lines().peekable()).is_err());
+
+    let root = build_tree(&mut "$ cd /\n$ ls\n18446744073709551615 a\n1 b".lines().peekable()).unwrap();
+    assert!(part1(&root, MAX_DIR_SIZE).is_err());
+    let root = build_tree(&mut "$ cd /\n$ ls\n80000000 a".lines().peekable()).unwrap();
+    assert!(part2(&root, DISK_SIZE, NEEDED_SPACE).is_err());
+  }
+
+}
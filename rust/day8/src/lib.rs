@@ -0,0 +1,176 @@
+use std::str::Lines;
+use std::cmp::max;
+
+pub fn count_visible_trees(lines: Lines<'_>) -> Result<i32, ParseGridError> {
+  let grid = parse(lines)?;
+  if grid.is_empty() {
+    return Ok(0);
+  }
+  let mut board: Vec<Vec<bool>> = grid.iter().map(|_| vec![false; grid[0].len()]).collect();
+  // dbg!(&grid, &board);
+
+  for i in 0..grid.len() {
+    let mut highest = -1;
+    for j in 0..grid[i].len() {
+      board[i][j] |= grid[i][j] > highest;
+      highest = max(highest, grid[i][j]);
+    }
+    highest = -1;
+    for j in (0..grid[i].len()).rev() {
+      board[i][j] |= grid[i][j] > highest;
+      highest = max(highest, grid[i][j]);
+    }
+  }
+  for j in 0..grid[0].len() {
+    let mut highest = -1;
+    for i in 0..grid.len() {
+      board[i][j] |= grid[i][j] > highest;
+      highest = max(highest, grid[i][j]);
+    }
+    highest = -1;
+    for i in (0..grid.len()).rev() {
+      board[i][j] |= grid[i][j] > highest;
+      highest = max(highest, grid[i][j]);
+    }
+  }
+
+  Ok(
+    board
+      .iter()
+      .flatten()
+      .filter(|i| **i)
+      .count() as i32
+  )
+}
+
+pub fn highest_scenic_score(lines: Lines<'_>) -> Result<i32, ParseGridError> {
+  let grid = parse(lines)?;
+  if grid.is_empty() {
+    return Ok(0);
+  }
+  let mut board: Vec<Vec<i32>> = grid.iter().map(|_| vec![0; grid[0].len()]).collect();
+
+  for i in 0..grid.len() {
+    for j in 0..grid[i].len() {
+      board[i][j] = 1;
+      let mut score = 0;
+      for col in (0..j).rev() {
+        score += 1;
+        if grid[i][col] >= grid[i][j] {
+          break;
+        }
+      }
+      board[i][j] *= score;
+      score = 0;
+      for col in (j + 1)..grid[i].len() {
+        score += 1;
+        if grid[i][col] >= grid[i][j] {
+          break;
+        }
+      }
+      board[i][j] *= score;
+      score = 0;
+      for row in (0..i).rev() {
+        score += 1;
+        if grid[row][j] >= grid[i][j] {
+          break;
+        }
+      }
+      board[i][j] *= score;
+      score = 0;
+      for row in (i+1)..grid.len() {
+        score += 1;
+        if grid[row][j] >= grid[i][j] {
+          break;
+        }
+      }
+      board[i][j] *= score;
+    }
+  }
+
+  // dbg!(&board);
+
+  Ok(
+    board
+      .iter()
+      .flatten()
+      .copied()
+      .max()
+      .unwrap_or(0)
+  )
+  // dbg!(&board);
+
+}
+
+// fn traverse(grid: &Vec<Vec<i32>>, board: &mut Vec<Vec<bool>>, i_range: &Vec<usize>, j_range: &Vec<usize>) {
+//   let mut highest = -1;
+//   // for i in i_range.0..i_range.1 {
+//   for i in i_range.clone() {
+//     for j in j_range.clone() {
+//       board[i][j] |= grid[i][j] > highest;
+//       highest = max(highest, grid[i][j]);
+//     }
+//   }
+// }
+
+pub fn parse(lines: Lines<'_>) -> Result<Vec<Vec<i32>>, ParseGridError> {
+  let grid = lines
+    .map(|s| s
+      .chars()
+      .map(|c| c
+        .to_digit(10)
+        .map(|d| d as i32)
+        .ok_or(ParseGridError { message: format!("not a valid tree height: {:?}", c) }))
+      .collect::<Result<Vec<_>, _>>())
+    .collect::<Result<Vec<_>, _>>()?;
+
+  if let Some(row) = grid.iter().find(|row| row.len() != grid[0].len()) {
+    return Err(ParseGridError {
+      message: format!("expected rows of {} trees, but found one with {}", grid[0].len(), row.len())
+    });
+  }
+
+  Ok(grid)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGridError {
+  pub message: String
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_visible_trees() {
+    assert_eq!(Ok(21), count_visible_trees(
+"30373
+25512
+65332
+33549
+35390".lines()));
+  }
+  #[test]
+  pub fn test_scenic_score() {
+    assert_eq!(Ok(8), highest_scenic_score(
+"30373
+25512
+65332
+33549
+35390".lines()));
+  }
+
+  #[test]
+  pub fn test_malformed_grid() {
+    assert!(count_visible_trees("12\n3a".lines()).is_err());
+    assert!(highest_scenic_score("12\n345".lines()).is_err());
+    assert_eq!(Ok(0), count_visible_trees("".lines()));
+    assert_eq!(Ok(0), highest_scenic_score("".lines()));
+  }
+
+  #[test]
+  pub fn test_vec_addressing() {
+    assert_eq!(8, vec![0,1,2,3,4,5,6,7,8][9 - 1]);
+  }
+}
@@ -0,0 +1,204 @@
+use std::{str::Lines, str::FromStr, collections::HashSet};
+use std::cmp::{min, max};
+
+pub fn parse_commands(lines: Lines<'_>) -> Result<Vec<(Direction, i32)>, ParseCommandError> {
+  lines
+    .map(|line| {
+      let (d, a) = line.split_once(' ')
+        .ok_or(ParseCommandError { message: format!("expected a direction and an amount: {}", line) })?;
+      Ok((
+        Direction::from_str(d)?,
+        a.parse::<i32>().map_err(|_e| ParseCommandError { message: format!("not a valid amount: {}", line) })?
+      ))
+    })
+    .collect()
+}
+
+// Returns the number of distinct positions the tail knot visits.
+pub fn simulate_movements(commands: &[(Direction, i32)], knot_count: usize) -> usize {
+  let mut moves = HashSet::new();
+  let mut knots = vec![Point{ x: 0, y: 0}; knot_count.max(1)];
+  moves.insert(*knots.last().unwrap());
+  // let board = debug::get_bounds(commands);
+  // debug::draw_board(&board, &knots);
+  for (d, a) in commands {
+    // println!("move {:?} {}", d, a);
+    for _i in 0..*a {
+      let head = knots.first_mut().unwrap();
+      head.r#move(d);
+      for j in 1..knot_count {
+        if !knots[j].is_adjacent(&knots[j - 1]) {
+          let tmp = knots[j - 1];
+          knots[j].move_adjacent(&tmp);
+        } else {
+          break;
+        }
+      }
+      moves.insert(*knots.last().unwrap());
+      // debug::draw_board(&board, &knots);
+      // println!("");
+    }
+  }
+
+  moves.len()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Point {
+  x: i32,
+  y: i32
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+  Up,
+  Down,
+  Right,
+  Left
+}
+
+impl Point {
+  pub fn r#move(&mut self, direction: &Direction) {
+    self.y += match direction {
+      Direction::Up => 1,
+      Direction::Down => -1,
+      _ => 0,
+    };
+    self.x += match direction {
+      Direction::Right => 1,
+      Direction::Left => -1,
+      _ => 0,
+    };
+  }
+
+  pub fn move_adjacent(&mut self, other: &Point) {
+    if self.is_adjacent(other) {
+      return;
+    }
+
+    // simplified logic because |diff_x|, |diff_x| never greater than 2
+    let diff_x = other.x - self.x;
+    let diff_y = other.y - self.y;
+    let sign_x = if diff_x < 0 { - 1 } else { 1 };
+    let sign_y = if diff_y < 0 { - 1 } else { 1 };
+    self.x += match diff_x.abs() {
+      1 => diff_x, // assumption if we're moving piece-meal, y must be 2
+      2 => sign_x * (sign_x * diff_x - 1),
+      _ => 0,
+    };
+    self.y += match diff_y.abs() {
+      1 => diff_y, // assumption if we're moving piece-meal, x must be 2
+      2 => sign_y * (sign_y * diff_y - 1),
+      _ => 0,
+    };
+  }
+
+  pub fn is_adjacent(&self, other: &Point) -> bool {
+    (self.x - other.x).abs() < 2 &&
+      (self.y - other.y).abs() < 2
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Move {
+  direction: Direction,
+  amount: u32,
+}
+
+impl FromStr for Direction {
+  type Err = ParseCommandError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "U" => Ok(Direction::Up),
+      "D" => Ok(Direction::Down),
+      "R" => Ok(Direction::Right),
+      "L" => Ok(Direction::Left),
+      _ => Err(ParseCommandError { message: format!("unknown direction: {}", s) })
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCommandError {
+  pub message: String
+}
+
+// some utils for debugging the movement
+pub mod debug {
+  use super::*;
+
+  #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+  pub struct Rect {
+    bottom_left: Point,
+    top_right: Point,
+  }
+
+  pub fn get_bounds(commands: &[(Direction, i32)]) -> Rect {
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (0, 0, 0, 0);
+    let mut point = Point { x: 0, y: 0 };
+    for (dir, amount) in commands {
+      for _i in 0..*amount {
+        point.r#move(dir);
+        min_x = min(point.x, min_x);
+        max_x = max(point.x, max_x);
+        min_y = min(point.y, min_y);
+        max_y = max(point.y, max_y);
+      }
+    }
+    Rect {
+      bottom_left: Point {
+        x: min_x,
+        y: min_y,
+      },
+      top_right: Point {
+        x: max_x,
+        y: max_y,
+      }
+    }
+  }
+
+  pub fn draw_board(board: &Rect, knots: &[Point]) {
+    println!("knots len: {}", knots.len());
+
+    for y in (board.bottom_left.y..board.top_right.y+1).rev() {
+      let mut s = String::new();
+      'next_x: for x in board.bottom_left.x..board.top_right.x+1 {
+        for (i, knot) in knots.iter().enumerate() {
+          if knot.x == x && knot.y == y {
+            let c = match i {
+              0 => 'H',
+              // knot_len => 'T',
+              _ => char::from_digit(i as u32, 10).unwrap(),
+            };
+            s.push(c);
+            continue 'next_x;
+          }
+        }
+        s.push('.');
+      }
+      println!("[{}]", s);
+    }
+
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_tail_moves() {
+    let commands = parse_commands(include_str!("../../inputs/day9.sample.txt").lines()).unwrap();
+    assert_eq!(13, simulate_movements(&commands, 2));
+    let commands = parse_commands(include_str!("../../inputs/day9.sample2.txt").lines()).unwrap();
+    assert_eq!(36, simulate_movements(&commands, 10));
+  }
+
+  #[test]
+  pub fn test_malformed_commands() {
+    assert!(parse_commands("X 1".lines()).is_err());
+    assert!(parse_commands("U".lines()).is_err());
+    assert!(parse_commands("U one".lines()).is_err());
+  }
+}
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        if let Ok(instructions) = day10::parse_instructions(content.lines()) {
+            let x_values = day10::register_values(&instructions);
+            let _ = day10::signal_strength(&x_values, day10::FIRST_SAMPLE, day10::SAMPLE_INTERVAL);
+            let _ = day10::render_crt(&x_values, day10::CRT_WIDTH, day10::CRT_HEIGHT);
+        }
+    }
+});
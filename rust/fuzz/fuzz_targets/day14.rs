@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        if let Ok(paths) = day14::parse_input(content.lines()) {
+            let _ = day14::min_units_for_infinite_flow(&paths, day14::SPIGOT.x);
+            let _ = day14::min_units_until_full(&paths, day14::SPIGOT.x);
+        }
+    }
+});
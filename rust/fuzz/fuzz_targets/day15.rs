@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Uses the sample's row and search area; the real input's 4,000,000 rows are too slow to
+// fuzz.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        if let Ok(pairs) = day15::parse_lines(content.lines()) {
+            let _ = day15::impossible_positions(&pairs, 10);
+            let _ = day15::find_open_position(&pairs, 20);
+        }
+    }
+});
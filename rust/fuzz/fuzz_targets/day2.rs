@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        let _ = day2::predict_score_from_move(content.lines());
+        let _ = day2::predict_score_from_result(content.lines());
+    }
+});
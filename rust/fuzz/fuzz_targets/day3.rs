@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        for alphabet in [day3::Alphabet::Letters, day3::Alphabet::Extended] {
+            let _ = day3::score_duplicate_item(content.lines(), alphabet);
+            let _ = day3::score_groups_unique_elements(content, day3::GROUP_SIZE, alphabet);
+        }
+    }
+});
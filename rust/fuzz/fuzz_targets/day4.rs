@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        let _ = day4::count_fully_contained_pairs(content.lines());
+        let _ = day4::count_overlapping_pairs(content.lines());
+        if let Ok(pairs) = day4::parse_pairs(content.lines()) {
+            let assignments: Vec<_> = pairs.iter().flat_map(|(a, b)| [*a, *b]).collect();
+            let _ = day4::coverage::coverage(&assignments);
+        }
+        let (pairs, errors) = day4::parse_pairs_lenient(content.lines());
+        assert_eq!(content.lines().count(), pairs.len() + errors.len());
+    }
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        let _ = day5::top_of_stacks_9000(content);
+        let _ = day5::top_of_stacks_9001(content);
+        let _ = day5::top_of_stacks(content, &day5::crane::LimitedCapacity::new(2).unwrap());
+        let _ = day5::top_of_stacks(content, &day5::crane::Rotating(1));
+        if let Ok(stacks) = day5::diagram::parse_stacks(content, 1) {
+            let diagram = day5::diagram::format_stacks(&stacks);
+            assert_eq!(Ok(stacks), day5::diagram::parse_stacks(&diagram, 1));
+        }
+    }
+});
@@ -0,0 +1,23 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// The first byte picks the marker length, so that short and zero length markers get
+// exercised as well as the puzzle's 4 and 14.
+fuzz_target!(|data: &[u8]| {
+    if let Some((n, data)) = data.split_first() {
+        if let Ok(content) = std::str::from_utf8(data) {
+            let n = (*n % 32) as usize;
+            let _ = day6::first_unique_chars(content, n);
+            let _ = day6::first_unique_chars_with_try_for_each(content, n);
+            // characters and bytes are the same for ASCII, so the old way is an oracle there.
+            let marker = day6::find_marker(content, n);
+            if content.is_ascii() && n > 0 {
+                assert_eq!(day6::first_unique_chars(content, n), marker.unwrap_or(0));
+            }
+            assert_eq!(vec![marker, marker], day6::find_markers(content, &[n, n]));
+            let first = day6::stream::markers(content.as_bytes(), n).next().map(|m| m.unwrap());
+            assert_eq!(marker, first);
+        }
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        if let Ok(tree) = day7::build_tree(&mut content.lines().peekable()) {
+            let _ = day7::part1(&tree, day7::MAX_DIR_SIZE);
+            let _ = day7::part2(&tree, day7::DISK_SIZE, day7::NEEDED_SPACE);
+        }
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        let _ = day8::count_visible_trees(content.lines());
+        let _ = day8::highest_scenic_score(content.lines());
+    }
+});
@@ -0,0 +1,20 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Simulating is linear in the total distance moved, so skip inputs that would just
+// spend their time walking a very long way.
+const MAX_STEPS: i64 = 1_000_000;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        if let Ok(commands) = day9::parse_commands(content.lines()) {
+            let steps: i64 = commands.iter().map(|(_, amount)| (*amount as i64).max(0)).sum();
+            if steps > MAX_STEPS {
+                return;
+            }
+            let _ = day9::simulate_movements(&commands, 2);
+            let _ = day9::simulate_movements(&commands, 10);
+        }
+    }
+});
@@ -0,0 +1,9 @@
+#!/bin/sh
+# Seeds each day's fuzz corpus with its puzzle inputs (and samples) from ../inputs.
+set -e
+cd "$(dirname "$0")"
+for input in ../inputs/day*.txt; do
+  day=$(basename "$input" | cut -d. -f1)
+  mkdir -p "corpus/$day"
+  cp "$input" "corpus/$day/"
+done
@@ -1,5 +1,7 @@
 pub struct Cli {
   pub path: std::path::PathBuf,
+  // anything given after the path, for days with more than one mode.
+  pub args: Vec<String>,
 }
 
 // NOTE: could use something like clap instead, but wanted to use only standard rust
@@ -9,6 +11,7 @@ impl Cli {
       let path = std::env::args().nth(1).expect("no path given");
       Cli {
           path: std::path::PathBuf::from(path),
+          args: std::env::args().skip(2).collect(),
       }
   }
 }
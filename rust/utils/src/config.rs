@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+// Puzzle parameters (marker lengths, disk sizes, which row to scan, ...) for each day, so
+// that variants and generated inputs can be solved without editing the code.
+//
+// The file has a table per day, and optionally a table per input file inside that, whose
+// values win when solving that file:
+//
+//   [day15]
+//   row = 2000000
+//   max_side = 4000000
+//
+//   [day15."day15.sample.txt"]
+//   row = 10
+//   max_side = 20
+//
+// Overrides (`day15.row=10`, usually from the command line) win over both.
+
+// Where `aoc run` and the days' own binaries look for the config when not told otherwise.
+pub const DEFAULT_PATH: &str = "aoc.toml";
+
+// The parameters each day reads, so that a misspelt one is an error rather than quietly
+// leaving the day on its default.
+const KEYS: [(u32, &[&str]); 6] = [
+  (3, &["group_size"]),
+  (6, &["packet_marker", "message_marker"]),
+  (7, &["max_dir_size", "disk_size", "needed_space"]),
+  (10, &["first_sample", "sample_interval", "crt_width", "crt_height"]),
+  (14, &["spigot_x"]),
+  (15, &["row", "max_side"]),
+];
+
+#[derive(Debug, Default, Clone)]
+pub struct Config {
+  days: HashMap<u32, DayConfig>,
+  overrides: HashMap<u32, Params>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct DayConfig {
+  params: Params,
+  inputs: HashMap<String, Params>,
+}
+
+// The parameters for solving one input. Anything that isn't set falls back to the default
+// the day was written for.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Params {
+  values: HashMap<String, i64>,
+}
+
+impl Config {
+  pub fn load(path: &Path) -> Result<Config, ConfigError> {
+    let content = std::fs::read_to_string(path).map_err(|e| ConfigError {
+      message: format!("could not read {}: {}", path.display(), e)
+    })?;
+    Config::parse(&content)
+  }
+
+  pub fn parse(content: &str) -> Result<Config, ConfigError> {
+    let table = content.parse::<toml::Table>().map_err(|e| ConfigError {
+      message: format!("not a valid config: {}", e)
+    })?;
+
+    let mut config = Config::default();
+    for (name, value) in table {
+      let day = parse_day(&name)?;
+      let keys = day_keys(day, &name)?;
+      let toml::Value::Table(table) = value else {
+        return Err(ConfigError { message: format!("{} should be a table", name) });
+      };
+      let day_config = config.days.entry(day).or_default();
+      for (key, value) in table {
+        match value {
+          toml::Value::Table(input) => {
+            let params = day_config.inputs.entry(key.clone()).or_default();
+            for (input_key, value) in input {
+              let name = format!("{}.\"{}\".{}", name, key, input_key);
+              check_key(keys, &input_key, &name)?;
+              params.values.insert(input_key, parse_value(&name, value)?);
+            }
+          }
+          value => {
+            let name = format!("{}.{}", name, key);
+            check_key(keys, &key, &name)?;
+            day_config.params.values.insert(key, parse_value(&name, value)?);
+          }
+        }
+      }
+    }
+    Ok(config)
+  }
+
+  // The config at DEFAULT_PATH, or an empty one if there isn't a file there.
+  pub fn load_default() -> Result<Config, ConfigError> {
+    let path = Path::new(DEFAULT_PATH);
+    if path.exists() { Config::load(path) } else { Ok(Config::default()) }
+  }
+
+  // Applies an override like `day15.row=10`.
+  pub fn set(&mut self, spec: &str) -> Result<(), ConfigError> {
+    let error = || ConfigError { message: format!("expected day<N>.<key>=<value>, got {}", spec) };
+    let (name, value) = spec.split_once('=').ok_or_else(error)?;
+    let (day, key) = name.split_once('.').ok_or_else(error)?;
+    let value = value.trim().parse::<i64>().map_err(|_| ConfigError {
+      message: format!("{} should be a whole number", name)
+    })?;
+    let (day, key) = (day.trim(), key.trim());
+    let number = parse_day(day)?;
+    check_key(day_keys(number, day)?, key, name.trim())?;
+    self.overrides.entry(number).or_default().values.insert(key.to_string(), value);
+    Ok(())
+  }
+
+  // The parameters for solving `day` on the input file named `input`.
+  pub fn params(&self, day: u32, input: &str) -> Params {
+    let mut params = Params::default();
+    if let Some(day_config) = self.days.get(&day) {
+      params.values.extend(day_config.params.values.clone());
+      if let Some(input_params) = day_config.inputs.get(input) {
+        params.values.extend(input_params.values.clone());
+      }
+    }
+    if let Some(overrides) = self.overrides.get(&day) {
+      params.values.extend(overrides.values.clone());
+    }
+    params
+  }
+}
+
+impl Params {
+  // The value for `key`, or `default` if it isn't set. Errors when the value doesn't fit,
+  // e.g. a negative size.
+  pub fn get<T: TryFrom<i64>>(&self, key: &str, default: T) -> Result<T, ConfigError> {
+    match self.values.get(key) {
+      Some(value) => T::try_from(*value).map_err(|_| ConfigError {
+        message: format!("{} = {} is out of range", key, value)
+      }),
+      None => Ok(default),
+    }
+  }
+}
+
+// Lists the parameters that are set, sorted by key, e.g. `max_side=20 row=10`.
+impl fmt::Display for Params {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut values: Vec<_> = self.values.iter().collect();
+    values.sort();
+    let values: Vec<_> = values.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+    write!(f, "{}", values.join(" "))
+  }
+}
+
+// The parameters for solving `day` on `input` from the default config, the same ones
+// `aoc run` would use, for the days' own binaries.
+pub fn default_params(day: u32, input: &Path) -> Result<Params, ConfigError> {
+  let name = input.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+  Ok(Config::load_default()?.params(day, &name))
+}
+
+fn parse_day(name: &str) -> Result<u32, ConfigError> {
+  name
+    .strip_prefix("day")
+    .and_then(|n| n.parse().ok())
+    .ok_or(ConfigError { message: format!("expected a day like day15, got {}", name) })
+}
+
+// The parameters `day`, named `name`, reads, or an error if it doesn't read any.
+fn day_keys(day: u32, name: &str) -> Result<&'static [&'static str], ConfigError> {
+  KEYS
+    .iter()
+    .find(|(d, _)| *d == day)
+    .map(|(_, keys)| *keys)
+    .ok_or(ConfigError { message: format!("{} doesn't take any parameters", name) })
+}
+
+fn check_key(keys: &[&str], key: &str, name: &str) -> Result<(), ConfigError> {
+  if keys.contains(&key) {
+    return Ok(());
+  }
+  Err(ConfigError { message: format!("unknown parameter {}, expected one of {}", name, keys.join(", ")) })
+}
+
+fn parse_value(name: &str, value: toml::Value) -> Result<i64, ConfigError> {
+  value.as_integer().ok_or(ConfigError { message: format!("{} should be a whole number", name) })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+  pub message: String
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  pub fn test_params() {
+    let mut config = Config::parse("
+[day7]
+disk_size = 100
+
+[day15]
+row = 2000000
+max_side = 4000000
+
+[day15.\"day15.sample.txt\"]
+row = 10
+max_side = 20
+").unwrap();
+
+    let params = config.params(15, "day15.txt");
+    assert_eq!(Ok(2000000), params.get("row", 0));
+    assert_eq!(Ok(4000000), params.get("max_side", 0));
+    let params = config.params(15, "day15.sample.txt");
+    assert_eq!(Ok(10), params.get("row", 0));
+    assert_eq!(Ok(20), params.get("max_side", 0));
+    assert_eq!("max_side=20 row=10", params.to_string());
+    assert_eq!("", config.params(6, "day6.txt").to_string());
+    assert_eq!(Ok(100_usize), config.params(7, "day7.txt").get("disk_size", 0));
+    assert_eq!(Ok(4_usize), config.params(6, "day6.txt").get("packet_marker", 4));
+
+    config.set("day15.row=11").unwrap();
+    assert_eq!(Ok(11), config.params(15, "day15.sample.txt").get("row", 0));
+    assert_eq!(Ok(20), config.params(15, "day15.sample.txt").get("max_side", 0));
+  }
+
+  #[test]
+  pub fn test_invalid_config() {
+    assert!(Config::parse("[day7").is_err());
+    assert!(Config::parse("[seven]\ndisk_size = 100").is_err());
+    assert!(Config::parse("day7 = 100").is_err());
+    assert!(Config::parse("[day7]\ndisk_size = \"big\"").is_err());
+    assert!(Config::parse("[day7.\"day7.txt\"]\ndisk_size = 1.5").is_err());
+
+    // misspelt parameters and days without any
+    let error = |content: &str| Config::parse(content).unwrap_err().message;
+    assert_eq!("unknown parameter day15.rwo, expected one of row, max_side", error("[day15]\nrwo = 10"));
+    assert_eq!(
+      "unknown parameter day15.\"day15.sample.txt\".rwo, expected one of row, max_side",
+      error("[day15.\"day15.sample.txt\"]\nrwo = 10")
+    );
+    assert_eq!("day2 doesn't take any parameters", error("[day2]\nrounds = 10"));
+
+    let mut config = Config::default();
+    assert!(config.set("day15.row").is_err());
+    assert!(config.set("row=10").is_err());
+    assert!(config.set("day15.row=ten").is_err());
+    assert_eq!(
+      "unknown parameter day7.disk_sise, expected one of max_dir_size, disk_size, needed_space",
+      config.set("day7.disk_sise=100").unwrap_err().message
+    );
+    assert_eq!("day2 doesn't take any parameters", config.set("day2.rounds=10").unwrap_err().message);
+    config.set("day7.disk_size=-1").unwrap();
+    assert!(config.params(7, "day7.txt").get::<usize>("disk_size", 0).is_err());
+  }
+}
@@ -0,0 +1,33 @@
+// A tiny xorshift generator, for the benches' generated inputs and day2's random strategy.
+//
+// NOTE: used rather than pulling in rand, so that the same seed gives the same numbers on
+// every machine and every run.
+pub struct Rng(u64);
+
+impl Rng {
+  pub fn new(seed: u64) -> Self {
+    Rng(seed.max(1))
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.0 ^= self.0 << 13;
+    self.0 ^= self.0 >> 7;
+    self.0 ^= self.0 << 17;
+    self.0
+  }
+
+  // a number in [lower, upper]
+  pub fn between(&mut self, lower: i64, upper: i64) -> i64 {
+    lower + (self.next_u64() % ((upper - lower + 1) as u64)) as i64
+  }
+
+  pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+    &items[self.between(0, items.len() as i64 - 1) as usize]
+  }
+
+  pub fn shuffle<T>(&mut self, items: &mut [T]) {
+    for i in (1..items.len()).rev() {
+      items.swap(i, self.between(0, i as i64) as usize);
+    }
+  }
+}